@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let day = advent::days::find(2023, 3).unwrap();
+        _ = (day.parse)(input);
+    }
+});
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, fmt::Display, io::ErrorKind, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+
+/// Known answers for one year, read from a manifest such as `answers/2023.txt`.
+///
+/// Each line holds a day, a part and the answer separated by whitespace, e.g.
+/// `05 1 35`. Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    answers: HashMap<(u8, u8), String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unverified,
+}
+
+impl Manifest {
+    /// Loads a manifest, treating a missing file as an empty one.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("reading {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut answers = HashMap::new();
+        for (line_num, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_ascii_whitespace();
+            let (Some(day), Some(part), Some(answer), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(anyhow!("line {}: expected `day part answer`", line_num + 1));
+            };
+            let day = day
+                .parse()
+                .with_context(|| format!("line {}: invalid day", line_num + 1))?;
+            let part = part
+                .parse()
+                .with_context(|| format!("line {}: invalid part", line_num + 1))?;
+            answers.insert((day, part), answer.to_owned());
+        }
+        Ok(Manifest { answers })
+    }
+
+    pub fn expected(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.expected(day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_owned(),
+            },
+            None => Verdict::Unverified,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected } => write!(f, "wrong, expected {}", expected),
+            Verdict::Unverified => write!(f, "unverified"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::parse(
+            "# day part answer
+01 1 142
+01 2 281
+
+5 1 35",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.check(1, 1, "142"), Verdict::Correct);
+        assert_eq!(
+            manifest.check(1, 2, "280"),
+            Verdict::Wrong {
+                expected: "281".to_owned()
+            }
+        );
+        assert_eq!(manifest.check(5, 2, "46"), Verdict::Unverified);
+        assert_eq!(manifest.expected(5, 1), Some("35"));
+        assert!(Manifest::parse("01 1").is_err());
+    }
+}
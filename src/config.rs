@@ -0,0 +1,212 @@
+use std::{collections::BTreeMap, io::ErrorKind, path::Path, path::PathBuf};
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub const DEFAULT_PATH: &str = "advent.toml";
+
+/// Project settings, read from `advent.toml`.
+///
+/// ```toml
+/// input_dir = "inputs"
+/// answers_dir = "answers"
+/// format = "json"
+///
+/// [days.2023.day11]
+/// expansion = 100
+/// ```
+///
+/// Every key is optional; command line flags take precedence over the file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Inputs are read from `<input_dir>/<year>/dayNN.txt`.
+    pub input_dir: PathBuf,
+    /// Answer manifests are read from `<answers_dir>/<year>.txt`.
+    pub answers_dir: PathBuf,
+    pub format: Format,
+    /// Solver parameters keyed by year, then by `dayNN`.
+    days: BTreeMap<String, BTreeMap<String, toml::Table>>,
+    /// Parameters applied on top of every day's table, set from the command line.
+    #[serde(skip)]
+    overrides: toml::Table,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            input_dir: PathBuf::from("inputs"),
+            answers_dir: PathBuf::from("answers"),
+            format: Format::default(),
+            days: BTreeMap::new(),
+            overrides: toml::Table::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads a config file, falling back to the defaults when it does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                Self::parse(&content).with_context(|| format!("reading {}", path.display()))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(toml::from_str(input)?)
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.input_dir
+            .join(year.to_string())
+            .join(format!("day{:02}.txt", day))
+    }
+
+    pub fn answers_path(&self, year: u16) -> PathBuf {
+        self.answers_dir.join(format!("{}.txt", year))
+    }
+
+    /// The config for a named input set, whose inputs live in
+    /// `<input_dir>/<name>/<year>/dayNN.txt` and answers in
+    /// `<answers_dir>/<name>/<year>.txt`.
+    pub fn with_set(&self, name: &str) -> Config {
+        Config {
+            input_dir: self.input_dir.join(name),
+            answers_dir: self.answers_dir.join(name),
+            ..self.clone()
+        }
+    }
+
+    /// Names of the input sets found in the input directory. Any
+    /// subdirectory that is not a year is treated as a set.
+    pub fn input_sets(&self) -> Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.input_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading {}", self.input_dir.display()))
+            }
+        };
+        let mut sets = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with('.') || name.parse::<u16>().is_ok() {
+                continue;
+            }
+            sets.push(name);
+        }
+        sets.sort();
+        Ok(sets)
+    }
+
+    /// Sets a parameter for every day from a `key=value` string. Values are
+    /// read as TOML where possible and as plain strings otherwise.
+    pub fn set_param(&mut self, assignment: &str) -> Result<()> {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| anyhow!("expected key=value, got `{}`", assignment))?;
+        let value = toml::from_str::<toml::Table>(&format!("v = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("v"))
+            .unwrap_or_else(|| toml::Value::String(value.to_owned()));
+        self.overrides.insert(key.trim().to_owned(), value);
+        Ok(())
+    }
+
+    pub fn params(&self, year: u16, day: u8) -> Params {
+        let mut table = self
+            .days
+            .get(&year.to_string())
+            .and_then(|days| days.get(&format!("day{:02}", day)))
+            .cloned()
+            .unwrap_or_default();
+        table.extend(self.overrides.clone());
+        Params(table)
+    }
+}
+
+/// Named solver parameters for a single day.
+#[derive(Debug, Default, Clone)]
+pub struct Params(toml::Table);
+
+impl Params {
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>> {
+        self.0
+            .get(key)
+            .map(|value| {
+                value
+                    .clone()
+                    .try_into()
+                    .with_context(|| format!("invalid value for parameter `{}`", key))
+            })
+            .transpose()
+    }
+
+    pub fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> Result<T> {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let mut config = Config::parse(
+            r#"
+input_dir = "data"
+format = "json"
+
+[days.2023.day08]
+start = "BBB"
+end = "YYY"
+"#,
+        )
+        .unwrap();
+        config.set_param("end=QQQ").unwrap();
+        config.set_param("expansion=10").unwrap();
+
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(
+            config.input_path(2023, 8),
+            Path::new("data").join("2023").join("day08.txt")
+        );
+        assert_eq!(config.answers_path(2023), Path::new("answers/2023.txt"));
+        assert_eq!(
+            config.with_set("alice").input_path(2023, 8),
+            Path::new("data/alice/2023/day08.txt")
+        );
+        assert_eq!(
+            config.with_set("alice").answers_path(2023),
+            Path::new("answers/alice/2023.txt")
+        );
+
+        let params = config.params(2023, 8);
+        assert_eq!(params.get::<String>("start").unwrap().unwrap(), "BBB");
+        assert_eq!(params.get::<String>("end").unwrap().unwrap(), "QQQ");
+        assert_eq!(params.get_or("expansion", 2usize).unwrap(), 10);
+        assert_eq!(config.params(2023, 1).get_or("missing", 7).unwrap(), 7);
+        assert!(params.get::<usize>("start").is_err());
+        assert!(config.set_param("no_equals").is_err());
+    }
+}
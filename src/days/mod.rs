@@ -1,36 +1,304 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-
-pub fn run() {
-    println!("Day 01 ***********");
-    day01::run().expect("uh oh");
-    println!("Day 02 ***********");
-    day02::run().expect("oops");
-    println!("Day 03 ***********");
-    day03::run().expect("oh no");
-    println!("Day 04 ***********");
-    day04::run().expect("something is awry");
-    println!("Day 05 ***********");
-    day05::run().expect("yikes");
-    println!("Day 06 ***********");
-    day06::run().expect("womp womp");
-    println!("Day 07 ***********");
-    day07::run().expect("card sharks");
-    println!("Day 08 ***********");
-    day08::run().expect("thonk");
-    println!("Day 09 ***********");
-    day09::run().expect("ugh");
-    println!("Day 10 ***********");
-    day10::run().expect("oops");
-    println!("Day 11 ***********");
-    day11::run().expect("thunk");
+use std::{cell::RefCell, fmt::Display, io::BufRead};
+
+use anyhow::Result;
+use rand::rngs::StdRng;
+use serde::Serialize;
+
+use crate::{
+    config::{Format, Params},
+    viz::Frames,
+};
+
+pub mod y2023;
+
+/// Every registered year, oldest first.
+const YEARS: &[&[Day]] = &[y2023::DAYS];
+
+pub type Solver = fn(&str, &Context) -> Result<Answers>;
+
+/// Reads a puzzle input into the day's model without solving it. Malformed
+/// input is reported as an error, never a panic.
+pub type Parser = fn(&str) -> Result<()>;
+
+/// Produces a random but well formed puzzle input. What `size` measures is
+/// up to each day, e.g. lines of input or the side of a grid.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Rewrites a puzzle input in the day's canonical form, rejecting malformed
+/// input like the parser does.
+pub type Normalizer = fn(&str) -> Result<String>;
+
+/// Writes a detailed account of how the day reads a puzzle input, as text
+/// or JSON, for checking the solution against the input by hand.
+pub type Reporter = fn(&str, &Params, Format) -> Result<String>;
+
+/// Solves a puzzle input read a piece at a time, for days whose answers
+/// never need the whole input at once.
+pub type Streamer = fn(&mut dyn BufRead, &Context) -> Result<Answers>;
+
+/// Everything a solver may consult besides its puzzle input.
+#[derive(Debug, Default)]
+pub struct Context {
+    pub params: Params,
+    pub trace: Trace,
+    pub frames: Frames,
+}
+
+/// Notes a solver makes on the way to its answers, for whoever wants to see
+/// how they were reached. Nothing is kept unless tracing was asked for.
+#[derive(Debug, Default)]
+pub struct Trace {
+    enabled: bool,
+    lines: RefCell<Vec<TraceLine>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceLine {
+    pub part: u8,
+    pub message: String,
+}
+
+impl Trace {
+    pub fn enabled() -> Self {
+        Trace {
+            enabled: true,
+            lines: RefCell::default(),
+        }
+    }
+
+    /// Whether notes are being kept, for solvers that need to work out
+    /// something extra only to explain it.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records a note about `part`. The message is only built when tracing.
+    pub fn note(&self, part: u8, message: impl FnOnce() -> String) {
+        if self.enabled {
+            self.lines.borrow_mut().push(TraceLine {
+                part,
+                message: message(),
+            });
+        }
+    }
+
+    pub fn into_lines(self) -> Vec<TraceLine> {
+        self.lines.into_inner()
+    }
+}
+
+/// A single registered puzzle solution.
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: Solver,
+    pub parse: Parser,
+    pub generate: Generator,
+    pub normalize: Option<Normalizer>,
+    pub stream: Option<Streamer>,
+    pub report: Option<Reporter>,
+}
+
+impl Day {
+    pub const fn new(
+        year: u16,
+        day: u8,
+        solve: Solver,
+        parse: Parser,
+        generate: Generator,
+    ) -> Self {
+        Day {
+            year,
+            day,
+            solve,
+            parse,
+            generate,
+            normalize: None,
+            stream: None,
+            report: None,
+        }
+    }
+
+    pub const fn with_normalize(mut self, normalize: Normalizer) -> Self {
+        self.normalize = Some(normalize);
+        self
+    }
+
+    pub const fn with_stream(mut self, stream: Streamer) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub const fn with_report(mut self, report: Reporter) -> Self {
+        self.report = Some(report);
+        self
+    }
+}
+
+/// The answers produced by a solver. Days that only solve one part leave
+/// the other empty.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Answers to questions beyond the puzzle's own, such as those asked
+    /// through a day's parameters. These are never checked.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<Extra>,
+}
+
+/// A named extra answer, or why there is none. One failing extra doesn't
+/// fail the day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Extra {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Display for Extra {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.answer, &self.error) {
+            (Some(answer), _) => write!(f, "{}: {}", self.name, answer),
+            (None, Some(error)) => write!(f, "{}: error: {}", self.name, error),
+            (None, None) => write!(f, "{}: no answer", self.name),
+        }
+    }
+}
+
+impl Answers {
+    pub fn with_part_1(mut self, answer: impl ToString) -> Self {
+        self.part_1 = Some(answer.to_string());
+        self
+    }
+
+    pub fn with_part_2(mut self, answer: impl ToString) -> Self {
+        self.part_2 = Some(answer.to_string());
+        self
+    }
+
+    /// Adds an extra answer called `name`, or its error.
+    pub fn with_extra(mut self, name: impl ToString, answer: Result<impl ToString>) -> Self {
+        let (answer, error) = match answer {
+            Ok(answer) => (Some(answer.to_string()), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+        self.extras.push(Extra {
+            name: name.to_string(),
+            answer,
+            error,
+        });
+        self
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn all() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    all().find(|d| d.year == year && d.day == day)
+}
+
+/// Registered days matching the given filters, where `None` matches anything.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<&'static Day> {
+    all()
+        .filter(|d| year.is_none_or(|y| d.year == y))
+        .filter(|d| day.is_none_or(|n| d.day == n))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stress;
+
+    use proptest::{prelude::*, sample::Index};
+
+    #[test]
+    fn test_select() {
+        assert_eq!(select(Some(2023), None).len(), y2023::DAYS.len());
+        assert_eq!(select(None, Some(5)).len(), 1);
+        assert!(select(Some(1999), None).is_empty());
+        assert_eq!(find(2023, 11).map(|d| d.day), Some(11));
+    }
+
+    /// Inputs that used to make a parser panic.
+    const PARSE_CRASHES: &[(u16, u8, &str)] = &[
+        (2023, 1, "abc\n"),
+        (2023, 1, "é"),
+        (2023, 2, "Game 1: 3 pur.ple\n"),
+        (2023, 2, "Game 1: 3\n"),
+        (2023, 3, "12 3\n"),
+        (2023, 3, "..\u{0}..\n"),
+        (2023, 3, "99999999999999999999\n"),
+        (2023, 4, "Card 1: 1 2 3\n"),
+        (2023, 5, "seeds: 1\n"),
+        (2023, 5, "seeds: 9223372036854775807 1\n"),
+        (2023, 5, "seeds: 1 2\n\nmap:\n1 2 0\n"),
+        (2023, 6, "Time: 7\n"),
+        (2023, 7, "32T3K\n"),
+        (2023, 7, "é2345 1\n"),
+        (2023, 8, "LR\n\nAAA = (BBB)\n"),
+        (2023, 9, "1 x 3\n"),
+        (2023, 10, "S-X\n"),
+        (2023, 11, ""),
+    ];
+
+    #[test]
+    fn test_parse_crashes_are_errors() {
+        for &(year, day, input) in PARSE_CRASHES {
+            let parse = find(year, day).unwrap().parse;
+            assert!(
+                parse(input).is_err(),
+                "{} day {} accepted {:?}",
+                year,
+                day,
+                input
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parsers_never_panic(input in any::<String>()) {
+            for day in all() {
+                _ = (day.parse)(&input);
+            }
+        }
+
+        /// Corrupting well formed input reaches much deeper into the parsers
+        /// than random text does.
+        #[test]
+        fn test_parsers_never_panic_on_corrupted_input(
+            seed in any::<u64>(),
+            size in 0..20usize,
+            at in any::<Index>(),
+            cut in any::<Index>(),
+            replacement in "[ -~\n]|\\PC",
+        ) {
+            for day in all() {
+                let mut chars: Vec<char> = stress::generate(day, size, seed).chars().collect();
+                if chars.is_empty() {
+                    continue;
+                }
+                let at = at.index(chars.len());
+                chars.splice(at..=at, replacement.chars());
+                _ = (day.parse)(&chars.iter().collect::<String>());
+                chars.truncate(cut.index(chars.len() + 1));
+                _ = (day.parse)(&chars.iter().collect::<String>());
+            }
+        }
+    }
 }
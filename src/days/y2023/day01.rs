@@ -0,0 +1,509 @@
+use std::{fmt::Display, fs, io::BufRead, path::PathBuf};
+
+use crate::{
+    config::Params,
+    days::{Answers, Context},
+};
+use anyhow::{anyhow, bail, Context as _, Result};
+use rand::{rngs::StdRng, Rng};
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let vocabulary = Vocabulary::from_params(&ctx.params)?;
+    if ctx.trace.is_enabled() {
+        for (part, vocabulary) in [(1, None), (2, Some(&vocabulary))] {
+            for explanation in explain(input, vocabulary) {
+                ctx.trace.note(part, || explanation.to_string());
+            }
+        }
+    }
+    Ok(Answers::default()
+        .with_part_1(part_1(input)?)
+        .with_part_2(part_2(input, &vocabulary)?))
+}
+
+/// There is no model beyond the calibration values themselves, so parsing
+/// reads them for both parts.
+pub fn parse(input: &str) -> Result<()> {
+    part_1(input)?;
+    part_2(input, &Vocabulary::default())?;
+    Ok(())
+}
+
+/// Solves from `reader` a line at a time, for documents too large to read
+/// in whole. Both parts are summed in the one pass.
+pub fn stream(reader: &mut dyn BufRead, ctx: &Context) -> Result<Answers> {
+    let vocabulary = Vocabulary::from_params(&ctx.params)?;
+    let [part_1, part_2] = calibration_sums(reader, [None, Some(&vocabulary)])?;
+    Ok(Answers::default().with_part_1(part_1).with_part_2(part_2))
+}
+
+fn part_1(input: &str) -> Result<u128> {
+    let [sum] = calibration_sums(input.as_bytes(), [None])?;
+    Ok(sum)
+}
+
+fn part_2(input: &str, vocabulary: &Vocabulary) -> Result<u128> {
+    let [sum] = calibration_sums(input.as_bytes(), [Some(vocabulary)])?;
+    Ok(sum)
+}
+
+/// Sums the calibration values of the lines `reader` yields once for each
+/// of `vocabularies`, reading spelled digits from it if there is one. Only
+/// one line is held at a time, so a document of any length can be summed.
+fn calibration_sums<const N: usize>(
+    mut reader: impl BufRead,
+    vocabularies: [Option<&Vocabulary>; N],
+) -> Result<[u128; N]> {
+    let mut sums = [0u128; N];
+    let mut line = String::new();
+    for n in 1.. {
+        line.clear();
+        if reader
+            .read_line(&mut line)
+            .with_context(|| format!("reading line {}", n))?
+            == 0
+        {
+            break;
+        }
+        // the same line endings as `str::lines`
+        let text = line.strip_suffix('\n').unwrap_or(&line);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        for (sum, vocabulary) in sums.iter_mut().zip(vocabularies) {
+            let value = calibration_value(text, vocabulary)
+                .with_context(|| format!("line {} has no digit", n))?;
+            *sum = sum
+                .checked_add(value as u128)
+                .context("calibration sum overflowed")?;
+        }
+    }
+    Ok(sums)
+}
+
+/// How each line's calibration value was read, with the lines that have
+/// none listed rather than failing the whole input.
+fn explain<'a>(input: &'a str, vocabulary: Option<&Vocabulary>) -> Vec<Explanation<'a>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, line)| Explanation {
+            line: n + 1,
+            ends: calibration_digits(line, vocabulary),
+        })
+        .collect()
+}
+
+/// A line's calibration value and the digits it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Explanation<'a> {
+    /// The line number, counting from 1.
+    line: usize,
+    /// The first and last digit, or `None` when the line has no digit.
+    ends: Option<(Digit<'a>, Digit<'a>)>,
+}
+
+/// A digit as it was found in a line, numeric or spelled out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Digit<'a> {
+    value: u64,
+    /// Byte offset of the digit in its line.
+    offset: usize,
+    /// The text read as the digit, the numeral itself or a number word.
+    text: &'a str,
+}
+
+impl Digit<'_> {
+    fn is_spelled(&self) -> bool {
+        !self.text.as_bytes()[0].is_ascii_digit()
+    }
+}
+
+impl Display for Explanation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ends {
+            Some((first, last)) => write!(
+                f,
+                "line {}: first {}, last {}, value {}",
+                self.line,
+                first,
+                last,
+                first.value * 10 + last.value
+            ),
+            None => write!(f, "line {}: no digit", self.line),
+        }
+    }
+}
+
+impl Display for Digit<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.is_spelled() {
+            "spelled"
+        } else {
+            "numeric"
+        };
+        write!(f, "`{}` at byte {} ({})", self.text, self.offset, kind)
+    }
+}
+
+/// The line's first digit followed by its last.
+fn calibration_value(line: &str, vocabulary: Option<&Vocabulary>) -> Option<u64> {
+    let (first, last) = calibration_digits(line, vocabulary)?;
+    Some(first.value * 10 + last.value)
+}
+
+/// The line's first and last digits, looking forward from the start for one
+/// and back from the end for the other. A digit is tried at every position,
+/// so spelled digits sharing letters like `eightwo` are both found.
+fn calibration_digits<'a>(
+    line: &'a str,
+    vocabulary: Option<&Vocabulary>,
+) -> Option<(Digit<'a>, Digit<'a>)> {
+    let digit = |offset: usize| {
+        let (value, len) = digit_at(&line.as_bytes()[offset..], vocabulary)?;
+        Some(Digit {
+            value,
+            offset,
+            // words are whole characters, so a match starting on a byte
+            // that begins one also ends on a boundary
+            text: &line[offset..offset + len],
+        })
+    };
+    let first = (0..line.len()).find_map(digit)?;
+    let last = (0..line.len()).rev().find_map(digit)?;
+    Some((first, last))
+}
+
+/// The digit `rest` starts with, if any, and how many bytes spell it.
+/// Spelled digits are matched on bytes, which never match part way through a
+/// character.
+fn digit_at(rest: &[u8], vocabulary: Option<&Vocabulary>) -> Option<(u64, usize)> {
+    match rest.first() {
+        Some(c @ b'0'..=b'9') => Some(((c - b'0') as u64, 1)),
+        _ => vocabulary?.digit_at(rest),
+    }
+}
+
+/// Number words from zero to nine.
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const SPANISH: [&str; 10] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const FRENCH: [&str; 10] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+
+/// The words part 2 reads as digits, kept as a trie so every word starting
+/// at a position is found in one walk, however many share a prefix.
+#[derive(Debug)]
+struct Vocabulary {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNode {
+    children: Vec<(u8, usize)>,
+    /// The digit of the word ending here.
+    digit: Option<u64>,
+}
+
+impl Vocabulary {
+    /// The vocabulary named by the `vocabulary` parameter, or the words
+    /// listed in the file at `words`.
+    fn from_params(params: &Params) -> Result<Self> {
+        if let Some(path) = params.get::<PathBuf>("words")? {
+            let words =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            return Self::parse(&words).with_context(|| format!("reading {}", path.display()));
+        }
+        let name: String = params.get_or("vocabulary", String::from("english"))?;
+        Self::builtin(&name)
+    }
+
+    fn builtin(name: &str) -> Result<Self> {
+        let (words, skip) = match name {
+            // the puzzle itself has no zero
+            "english" => (ENGLISH, 1),
+            "english-zero" => (ENGLISH, 0),
+            "german" => (GERMAN, 0),
+            "spanish" => (SPANISH, 0),
+            "french" => (FRENCH, 0),
+            _ => bail!(
+                "unknown vocabulary `{}`, expected english, english-zero, german, spanish or french",
+                name
+            ),
+        };
+        Self::new(words.into_iter().zip(0..).skip(skip))
+    }
+
+    /// Reads one `word digit` pair per line. Blank lines and lines starting
+    /// with `#` are ignored.
+    fn parse(input: &str) -> Result<Self> {
+        let words = input
+            .lines()
+            .enumerate()
+            .map(|(n, line)| (n, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(n, line)| {
+                let mut fields = line.split_whitespace();
+                let (Some(word), Some(digit), None) = (fields.next(), fields.next(), fields.next())
+                else {
+                    bail!("line {}: expected `word digit`", n + 1);
+                };
+                match digit.parse() {
+                    Ok(digit @ 0..=9) => Ok((word, digit)),
+                    _ => Err(anyhow!("line {}: `{}` is not a digit", n + 1, digit)),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(words)
+    }
+
+    fn new<'a>(words: impl IntoIterator<Item = (&'a str, u64)>) -> Result<Self> {
+        let mut vocabulary = Vocabulary {
+            nodes: vec![TrieNode::default()],
+        };
+        for (word, digit) in words {
+            if word.is_empty() {
+                bail!("empty number word");
+            }
+            let mut node = 0;
+            for &byte in word.as_bytes() {
+                node = match vocabulary.child(node, byte) {
+                    Some(child) => child,
+                    None => {
+                        vocabulary.nodes.push(TrieNode::default());
+                        let child = vocabulary.nodes.len() - 1;
+                        vocabulary.nodes[node].children.push((byte, child));
+                        child
+                    }
+                };
+            }
+            match vocabulary.nodes[node].digit.replace(digit) {
+                Some(other) if other != digit => {
+                    bail!("`{}` stands for both {} and {}", word, other, digit)
+                }
+                _ => (),
+            }
+        }
+        Ok(vocabulary)
+    }
+
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(b, _)| *b == byte)
+            .map(|&(_, child)| child)
+    }
+
+    /// The digit of the longest word `rest` starts with, and that word's
+    /// length in bytes.
+    fn digit_at(&self, rest: &[u8]) -> Option<(u64, usize)> {
+        let mut node = 0;
+        let mut found = None;
+        for (len, &byte) in (1..).zip(rest) {
+            let Some(child) = self.child(node, byte) else {
+                break;
+            };
+            node = child;
+            if let Some(digit) = self.nodes[node].digit {
+                found = Some((digit, len));
+            }
+        }
+        found
+    }
+}
+
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Self::builtin("english").expect("the built-in vocabularies are valid")
+    }
+}
+
+/// Generates `size` calibration lines of letters, digits and spelled out
+/// digits. Every line holds at least one numeric digit.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let tokens = rng.gen_range(1..=8);
+        let digit_at = rng.gen_range(0..tokens);
+        for token in 0..tokens {
+            match rng.gen_range(0..3) {
+                _ if token == digit_at => output.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                0 => output.push(char::from(rng.gen_range(b'1'..=b'9'))),
+                1 => output.push_str(ENGLISH[rng.gen_range(1..ENGLISH.len())]),
+                _ => output.push(char::from(rng.gen_range(b'a'..=b'z'))),
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::Trace, util};
+    extern crate test;
+
+    use test::Bencher;
+
+    #[test]
+    fn test_part_1() {
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        assert_eq!(part_1(input).unwrap(), 142);
+        assert!(part_1("abc\n").is_err());
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+        let english = Vocabulary::default();
+        assert_eq!(part_2(input, &english).unwrap(), 281);
+        // overlapping words at either end
+        assert_eq!(calibration_value("eightwo", Some(&english)), Some(82));
+        assert_eq!(calibration_value("oneight", Some(&english)), Some(18));
+        assert_eq!(
+            calibration_value("twoneighthreeé", Some(&english)),
+            Some(23)
+        );
+        assert_eq!(calibration_value("sevenine", None), None);
+        assert_eq!(calibration_value("zero", Some(&english)), None);
+    }
+
+    /// Yields `line` over and over, `count` times, without ever holding more
+    /// than one copy of it.
+    struct Repeat {
+        line: &'static [u8],
+        at: usize,
+        count: usize,
+    }
+
+    impl std::io::Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.count == 0 {
+                return Ok(0);
+            }
+            let n = buf.len().min(self.line.len() - self.at);
+            buf[..n].copy_from_slice(&self.line[self.at..self.at + n]);
+            self.at += n;
+            if self.at == self.line.len() {
+                self.at = 0;
+                self.count -= 1;
+            }
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_calibration_sums_stream() {
+        let mut lines = std::io::BufReader::new(Repeat {
+            line: b"nine8seven\r\n",
+            at: 0,
+            count: 200_000,
+        });
+        let answers = stream(&mut lines, &Context::default()).unwrap();
+        assert_eq!(answers.part_1, Some((88 * 200_000).to_string()));
+        assert_eq!(answers.part_2, Some((97 * 200_000).to_string()));
+
+        let error = calibration_sums(&b"12\n\xff3\n"[..], [None]).unwrap_err();
+        assert_eq!(error.to_string(), "reading line 2");
+        // a line without a newline ends the document
+        assert_eq!(calibration_sums(&b"12\n3"[..], [None]).unwrap(), [45]);
+    }
+
+    #[test]
+    fn test_explain() {
+        let english = Vocabulary::default();
+        let explanations = explain("a1b2c\nxtwone3four\nabc\n", Some(&english));
+        assert_eq!(
+            explanations[1].to_string(),
+            "line 2: first `two` at byte 1 (spelled), last `four` at byte 7 (spelled), value 24"
+        );
+        assert_eq!(
+            explanations[0].to_string(),
+            "line 1: first `1` at byte 1 (numeric), last `2` at byte 3 (numeric), value 12"
+        );
+        assert_eq!(explanations[2].to_string(), "line 3: no digit");
+
+        // offsets count bytes, not characters
+        let french = Vocabulary::builtin("french").unwrap();
+        let (first, last) = calibration_digits("éézéro7", Some(&french)).unwrap();
+        assert_eq!((first.offset, first.text, first.value), (4, "zéro", 0));
+        assert_eq!((last.offset, last.is_spelled()), (9, false));
+
+        let ctx = Context {
+            trace: Trace::enabled(),
+            ..Context::default()
+        };
+        assert!(solve("1abc\nonetwo\n", &ctx).is_err());
+        let lines = ctx.trace.into_lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1].message, "line 2: no digit");
+        assert!(lines[3].message.ends_with("value 12"));
+    }
+
+    #[test]
+    fn test_vocabularies() {
+        let value = |name: &str, line: &str| {
+            calibration_value(line, Some(&Vocabulary::builtin(name).unwrap()))
+        };
+        assert_eq!(value("english-zero", "xzeroneight"), Some(8));
+        assert_eq!(value("german", "fünfzigsiebenull"), Some(50));
+        // seis and siete share a prefix
+        assert_eq!(value("spanish", "sietexseis"), Some(76));
+        assert_eq!(value("french", "zérounfneuf"), Some(9));
+        assert!(Vocabulary::builtin("klingon").is_err());
+
+        // the longest word starting at a position wins
+        let custom = Vocabulary::parse("# prefixes\nab 1\nabc 2\n\nbc 3\n").unwrap();
+        assert_eq!(calibration_value("abc", Some(&custom)), Some(23));
+        assert_eq!(calibration_value("xabx", Some(&custom)), Some(11));
+        assert!(Vocabulary::parse("ab 10\n").is_err());
+        assert!(Vocabulary::parse("ab 1\nab 2\n").is_err());
+    }
+
+    #[test]
+    fn test_vocabulary_from_params() {
+        let path = std::env::temp_dir().join(format!("advent-day01-{}.txt", std::process::id()));
+        fs::write(&path, "uno 1\nduo 2\n").unwrap();
+        let config = crate::config::Config::parse(&format!(
+            "[days.2023.day01]\nwords = {:?}\n",
+            path.display().to_string()
+        ))
+        .unwrap();
+        let vocabulary = Vocabulary::from_params(&config.params(2023, 1)).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(calibration_value("xduoxunox", Some(&vocabulary)), Some(21));
+        assert_eq!(calibration_value("two", Some(&vocabulary)), None);
+
+        let config =
+            crate::config::Config::parse("[days.2023.day01]\nvocabulary = \"french\"\n").unwrap();
+        let vocabulary = Vocabulary::from_params(&config.params(2023, 1)).unwrap();
+        assert_eq!(calibration_value("huit", Some(&vocabulary)), Some(88));
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 1);
+        b.iter(|| part_1(&input));
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 1);
+        let vocabulary = Vocabulary::default();
+        b.iter(|| part_2(&input, &vocabulary));
+    }
+}
@@ -0,0 +1,237 @@
+//! The most likely contents of the bag behind a game, taking each round as
+//! a handful drawn without replacement from the full bag and put back
+//! before the next.
+//!
+//! A round showing `x` cubes of each color from a bag holding `c` of them,
+//! `N` in all, has the multivariate hypergeometric likelihood
+//! `Π C(c, x) / C(N, Σx)`. For a fixed `N` the denominator is the same for
+//! every bag, and each color's `Σ log C(c, x)` over the rounds is concave in
+//! `c`, so handing out cubes one at a time to whichever color gains most
+//! from it finds the best bag.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use anyhow::{anyhow, bail, Result};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use super::{Bag, Color, Game};
+
+/// Most cubes a bag may hold to be estimated or weighed, as the work grows
+/// with the bag's size.
+const MAX_SIZE: usize = 100_000;
+
+/// The most likely bag for a game and how likely the game is under it.
+#[derive(Debug, PartialEq)]
+pub(super) struct Estimate {
+    pub(super) bag: Bag,
+    /// The natural log of the probability of the rounds the game showed.
+    pub(super) log_likelihood: f64,
+}
+
+/// The bag of `size` cubes, over the colors of `colors` and those the game
+/// shows, that makes the game most likely, or `None` if no bag of that size
+/// could have made it.
+pub(super) fn estimate(game: &Game, colors: &Bag, size: usize) -> Result<Option<Estimate>> {
+    if size > MAX_SIZE {
+        bail!("can't estimate a bag of more than {} cubes", MAX_SIZE);
+    }
+    let rounds = rounds(game)?;
+    let mut counts: BTreeMap<Color, usize> = colors.0.keys().map(|c| (c.clone(), 0)).collect();
+    for round in rounds.iter() {
+        for (color, &shown) in round {
+            let count = counts.entry(color.clone()).or_default();
+            *count = (*count).max(shown);
+        }
+    }
+
+    // every color starts with the fewest cubes the game needs of it
+    let needed = counts
+        .values()
+        .try_fold(0usize, |needed, &count| needed.checked_add(count));
+    let Some(mut spare) = needed.and_then(|needed| size.checked_sub(needed)) else {
+        return Ok(None);
+    };
+    // C(c + 1, x) / C(c, x) = (c + 1) / (c + 1 - x), and no color ever
+    // holds fewer cubes than a round shows of it
+    let gain = |color: &Color, count: usize| -> f64 {
+        rounds
+            .iter()
+            .map(|round| {
+                let shown = round.get(color).copied().unwrap_or(0);
+                ((count + 1) as f64).ln() - ((count + 1 - shown) as f64).ln()
+            })
+            .sum()
+    };
+    while spare > 0 {
+        let Some((best, _)) = counts
+            .iter()
+            .map(|(color, &count)| (color, gain(color, count)))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+        else {
+            return Ok(None);
+        };
+        let best = best.clone();
+        *counts.get_mut(&best).unwrap() += 1;
+        spare -= 1;
+    }
+
+    let bag = Bag(counts);
+    let log_likelihood = log_likelihood(game, &bag)?;
+    Ok(Some(Estimate {
+        bag,
+        log_likelihood,
+    }))
+}
+
+/// A game's most likely bag, weighed against the configured one.
+#[derive(Debug)]
+pub(super) struct Weighing {
+    estimate: Estimate,
+    /// The natural log of the probability of the game under the configured
+    /// bag.
+    configured: f64,
+}
+
+/// The most likely bag of `size` cubes behind the game, as [`estimate`]
+/// finds it, alongside how likely the game is under `bag`. Fails if no bag
+/// of that size could have made the game.
+pub(super) fn weigh(game: &Game, bag: &Bag, size: usize) -> Result<Weighing> {
+    let estimate = estimate(game, bag, size)?
+        .ok_or_else(|| anyhow!("no bag of {} cubes explains it", size))?;
+    Ok(Weighing {
+        estimate,
+        configured: log_likelihood(game, bag)?,
+    })
+}
+
+/// The natural log of the probability of the game's rounds being drawn from
+/// `bag`, negative infinity if they can't be.
+pub(super) fn log_likelihood(game: &Game, bag: &Bag) -> Result<f64> {
+    let size = bag
+        .0
+        .values()
+        .fold(0usize, |size, &count| size.saturating_add(count));
+    if size > MAX_SIZE {
+        bail!("can't weigh a bag of more than {} cubes", MAX_SIZE);
+    }
+    Ok(rounds(game)?
+        .iter()
+        .map(|round| {
+            // a round can't draw more cubes than the bag holds
+            let drawn = round
+                .values()
+                .try_fold(0usize, |drawn, &shown| drawn.checked_add(shown))
+                .filter(|&drawn| drawn <= size);
+            let Some(drawn) = drawn else {
+                return f64::NEG_INFINITY;
+            };
+            round
+                .iter()
+                .map(|(color, &shown)| ln_choose(bag.count(color), shown))
+                .sum::<f64>()
+                - ln_choose(size, drawn)
+        })
+        .sum())
+}
+
+/// How many cubes of each color each round showed.
+fn rounds(game: &Game) -> Result<Vec<BTreeMap<Color, usize>>> {
+    Ok(game
+        .normalized()?
+        .pulls
+        .into_iter()
+        .map(|round| round.into_iter().map(|pull| (pull.1, pull.0)).collect())
+        .collect())
+}
+
+/// The natural log of `n` choose `k`.
+fn ln_choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (0..k)
+        .map(|j| ((n - j) as f64).ln() - ((j + 1) as f64).ln())
+        .sum()
+}
+
+impl Display for Estimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "most likely bag {}, likelihood {:.3e}",
+            self.bag,
+            self.log_likelihood.exp()
+        )
+    }
+}
+
+impl Display for Weighing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, against {:.3e} for the configured bag",
+            self.estimate,
+            self.configured.exp()
+        )
+    }
+}
+
+impl Serialize for Weighing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut weighing = serializer.serialize_struct("Weighing", 3)?;
+        weighing.serialize_field("bag", &self.estimate.bag)?;
+        weighing.serialize_field("likelihood", &self.estimate.log_likelihood.exp())?;
+        weighing.serialize_field("configured_likelihood", &self.configured.exp())?;
+        weighing.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(5, 0), 0.0);
+        assert!((ln_choose(5, 2).exp() - 10.0).abs() < 1e-9);
+        assert!((ln_choose(40, 20).exp() - 137846528820.0).abs() < 1.0);
+        assert_eq!(ln_choose(2, 3), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_estimate() {
+        let game: Game = "Game 1: 1 red, 1 blue; 1 red, 1 blue".parse().unwrap();
+        let colors = Bag::default();
+        let best = estimate(&game, &colors, 4).unwrap().unwrap();
+        assert_eq!(best.bag.to_string(), "2 blue, 0 green, 2 red");
+
+        // the greedy bag beats every other bag of the same size
+        for blue in 0..=4 {
+            for red in 0..=4 - blue {
+                let other = Bag(BTreeMap::from([
+                    (Color(String::from("blue")), blue),
+                    (Color(String::from("green")), 4 - blue - red),
+                    (Color(String::from("red")), red),
+                ]));
+                assert!(log_likelihood(&game, &other).unwrap() <= best.log_likelihood + 1e-12);
+            }
+        }
+        // two draws of one red and one blue from two of each
+        let p = (2.0 * 2.0 / 6.0f64).powi(2);
+        assert!((best.log_likelihood.exp() - p).abs() < 1e-12);
+
+        assert_eq!(estimate(&game, &colors, 1).unwrap(), None);
+
+        let game: Game = "Game 1: 18446744073709551615 red, 1 red".parse().unwrap();
+        assert!(estimate(&game, &colors, 4).is_err());
+
+        // huge bags and draws are refused or impossible, never a long wait
+        let game: Game = "Game 1: 18446744073709551615 red, 1 blue".parse().unwrap();
+        assert!(estimate(&game, &colors, usize::MAX).is_err());
+        assert_eq!(estimate(&game, &colors, 4).unwrap(), None);
+        assert_eq!(log_likelihood(&game, &colors).unwrap(), f64::NEG_INFINITY);
+        let huge = Bag(BTreeMap::from([(Color(String::from("red")), usize::MAX)]));
+        assert!(log_likelihood(&game, &huge).is_err());
+    }
+}
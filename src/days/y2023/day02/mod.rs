@@ -0,0 +1,683 @@
+use crate::{
+    config::{Format, Params},
+    days::{Answers, Context},
+};
+use anyhow::{anyhow, bail, Context as _, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use query::Query;
+use report::GameReport;
+
+mod estimate;
+mod query;
+mod report;
+
+const MAX_PULL_RED: usize = 12;
+const MAX_PULL_GREEN: usize = 13;
+const MAX_PULL_BLUE: usize = 14;
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let bag = Bag::from_params(&ctx.params)?;
+    let game_list = Game::new_from_list(input)?;
+    let estimate_size = estimate_size(&bag, &ctx.params)?;
+    let mut estimates = Vec::new();
+    for game in game_list.iter() {
+        ctx.trace.note(1, || {
+            let violations = game.violations(&bag);
+            if violations.is_empty() {
+                format!("game {}: possible", game.id)
+            } else {
+                format!(
+                    "game {}: impossible, {}",
+                    game.id,
+                    violations.iter().join(", ")
+                )
+            }
+        });
+        ctx.trace.note(2, || match game.power(&bag) {
+            Ok(power) => format!(
+                "game {}: minimal bag {}, power {}",
+                game.id,
+                game.minimal_bag(),
+                power
+            ),
+            Err(e) => format!(
+                "game {}: minimal bag {}, {}",
+                game.id,
+                game.minimal_bag(),
+                e
+            ),
+        });
+        if let Some(size) = estimate_size {
+            let weighing = estimate::weigh(game, &bag, size);
+            ctx.trace.note(2, || match &weighing {
+                Ok(weighing) => format!("game {}: {}", game.id, weighing),
+                Err(e) => format!("game {}: {:#}", game.id, e),
+            });
+            estimates.push((game.id, weighing));
+        }
+    }
+    let mut answers = Answers::default()
+        .with_part_1(part_1(&game_list, &bag)?)
+        .with_part_2(part_2(&game_list, &bag)?);
+    if let Some(query) = ctx.params.get::<String>("query")? {
+        let parsed: Query = query
+            .parse()
+            .context("invalid value for parameter `query`")?;
+        answers = answers.with_extra(format!("query `{}`", query), parsed.run(&game_list, &bag));
+    }
+    for (id, weighing) in estimates {
+        answers = answers.with_extra(format!("game {} estimate", id), weighing);
+    }
+    Ok(answers)
+}
+
+/// The size of bag to estimate the contents of, by default the configured
+/// bag's, or `None` unless `estimate` is set.
+fn estimate_size(bag: &Bag, params: &Params) -> Result<Option<usize>> {
+    if !params.get_or("estimate", false)? {
+        return Ok(None);
+    }
+    let size = bag
+        .0
+        .values()
+        .fold(0usize, |size, &count| size.saturating_add(count));
+    Ok(Some(params.get_or("bag_size", size)?))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    Game::new_from_list(input).map(drop)
+}
+
+/// Reports on every game against the configured bag, which pulls make it
+/// impossible and its minimal bag, and its most likely bag when estimating.
+pub fn report(input: &str, params: &Params, format: Format) -> Result<String> {
+    let bag = Bag::from_params(params)?;
+    let estimate_size = estimate_size(&bag, params)?;
+    let games = Game::new_from_list(input)?;
+    let reports: Vec<GameReport> = games
+        .iter()
+        .map(|game| GameReport::new(game, &bag, estimate_size))
+        .collect();
+    Ok(match format {
+        Format::Text => reports.iter().map(GameReport::to_string).collect(),
+        Format::Json => serde_json::to_string_pretty(&reports)? + "\n",
+    })
+}
+
+fn part_1(games: &[Game], bag: &Bag) -> Result<usize> {
+    games
+        .iter()
+        .filter(|&game| game.is_possible(bag))
+        .try_fold(0usize, |sum, game| {
+            sum.checked_add(game.id)
+                .ok_or_else(|| anyhow!("sum of possible game ids overflowed"))
+        })
+}
+
+fn part_2(games: &[Game], bag: &Bag) -> Result<usize> {
+    games.iter().try_fold(0usize, |sum, game| {
+        sum.checked_add(game.power(bag)?)
+            .ok_or_else(|| anyhow!("sum of powers overflowed"))
+    })
+}
+
+/// How many cubes of each color the bag holds, and so the most a pull may
+/// show for a game to be possible. The bag holds none of a color it doesn't
+/// list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Bag(BTreeMap<Color, usize>);
+
+impl Bag {
+    /// The bag given by the `bag` table, e.g. `{ red = 12, teal = 3 }`, or
+    /// the puzzle's red, green and blue cubes. Either way `max_red`,
+    /// `max_green` and `max_blue` override those colors.
+    fn from_params(params: &Params) -> Result<Self> {
+        let mut bag = match params.get::<BTreeMap<String, usize>>("bag")? {
+            Some(contents) => Bag(contents
+                .into_iter()
+                .map(|(color, count)| Ok((color.parse()?, count)))
+                .collect::<Result<_>>()
+                .context("invalid value for parameter `bag`")?),
+            None => Bag::default(),
+        };
+        for color in ["red", "green", "blue"] {
+            if let Some(count) = params.get(&format!("max_{}", color))? {
+                bag.0.insert(Color(color.to_owned()), count);
+            }
+        }
+        Ok(bag)
+    }
+
+    fn count(&self, color: &Color) -> usize {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+}
+
+impl Default for Bag {
+    fn default() -> Self {
+        Bag(BTreeMap::from([
+            (Color(String::from("red")), MAX_PULL_RED),
+            (Color(String::from("green")), MAX_PULL_GREEN),
+            (Color(String::from("blue")), MAX_PULL_BLUE),
+        ]))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Pull(usize, Color);
+
+impl FromStr for Pull {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut fields = value.split_whitespace();
+        let (Some(count), Some(color), None) = (fields.next(), fields.next(), fields.next()) else {
+            bail!("expected `<count> <color>`, got `{}`", value.trim());
+        };
+        Ok(Pull(count.parse()?, color.parse()?))
+    }
+}
+
+/// A pull showing more cubes of a color than the bag holds.
+#[derive(Debug, PartialEq, Eq)]
+struct Violation<'a> {
+    /// The round the pull was in, counting from 1.
+    round: usize,
+    pull: &'a Pull,
+    /// How many cubes of the pull's color the bag holds.
+    limit: usize,
+}
+
+impl Display for Violation<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} pulled {}, {} more than the bag's {}",
+            self.round,
+            self.pull,
+            self.pull.0 - self.limit,
+            self.limit
+        )
+    }
+}
+
+/// A cube color, any single word.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+struct Color(String);
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let is_word = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+        if value.is_empty() || !value.chars().all(is_word) {
+            bail!("invalid pull color `{}`", value);
+        }
+        Ok(Color(value.to_owned()))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    id: usize,
+    pulls: Vec<Vec<Pull>>,
+}
+
+impl Game {
+    fn new_from_list(games: &str) -> Result<Vec<Game>> {
+        games
+            .lines()
+            .enumerate()
+            .map(|(n, line)| line.parse().with_context(|| format!("line {}", n + 1)))
+            .collect()
+    }
+
+    fn is_possible(&self, bag: &Bag) -> bool {
+        self.pulls
+            .iter()
+            .flatten()
+            .all(|pull| pull.0 <= bag.count(&pull.1))
+    }
+
+    /// The same game with each round's pulls of a color merged into one and
+    /// sorted by color, or an error if a merged count overflows.
+    fn normalized(&self) -> Result<Game> {
+        let pulls = self
+            .pulls
+            .iter()
+            .enumerate()
+            .map(|(n, round)| {
+                let mut shown: BTreeMap<&Color, usize> = BTreeMap::new();
+                for Pull(count, color) in round {
+                    let total = shown.entry(color).or_default();
+                    *total = total.checked_add(*count).ok_or_else(|| {
+                        anyhow!("game {} round {} shows too many {}", self.id, n + 1, color)
+                    })?;
+                }
+                Ok(shown
+                    .into_iter()
+                    .map(|(color, count)| Pull(count, color.clone()))
+                    .collect())
+            })
+            .collect::<Result<_>>()?;
+        Ok(Game { id: self.id, pulls })
+    }
+
+    /// Every pull that shows more cubes than `bag` holds, in the order they
+    /// were made.
+    fn violations<'a>(&'a self, bag: &Bag) -> Vec<Violation<'a>> {
+        self.pulls
+            .iter()
+            .enumerate()
+            .flat_map(|(n, round)| round.iter().map(move |pull| (n + 1, pull)))
+            .filter_map(|(round, pull)| {
+                let limit = bag.count(&pull.1);
+                (pull.0 > limit).then_some(Violation { round, pull, limit })
+            })
+            .collect()
+    }
+
+    /// The fewest cubes of each color shown that could have made the game.
+    fn minimal_bag(&self) -> Bag {
+        let mut bag = Bag(BTreeMap::new());
+        for Pull(count, color) in self.pulls.iter().flatten() {
+            let most = bag.0.entry(color.clone()).or_default();
+            *most = (*most).max(*count);
+        }
+        bag
+    }
+
+    /// The product of the minimal bag's counts, over the colors in `bag` as
+    /// well as those shown, so a color of the bag the game never shows
+    /// makes the power zero.
+    fn power(&self, bag: &Bag) -> Result<usize> {
+        let minimal = self.minimal_bag();
+        let factors: Vec<usize> = bag
+            .0
+            .keys()
+            .filter(|color| !minimal.0.contains_key(color))
+            .map(|color| minimal.count(color))
+            .chain(minimal.0.values().copied())
+            .collect();
+        if factors.contains(&0) {
+            return Ok(0);
+        }
+        factors
+            .into_iter()
+            .try_fold(1usize, |power, factor| power.checked_mul(factor))
+            .ok_or_else(|| anyhow!("power of game {} overflowed", self.id))
+    }
+}
+
+/// Reads a game from its canonical form, allowing any whitespace around the
+/// separators.
+impl FromStr for Game {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let (id, pulls) = value
+            .split_once(':')
+            .ok_or_else(|| anyhow!("expected `Game <id>: <pulls>`"))?;
+        let Some(("Game", id)) = id.split_whitespace().collect_tuple() else {
+            bail!("expected `Game <id>`, got `{}`", id.trim());
+        };
+        let pulls: Vec<Vec<Pull>> = pulls
+            .split(';')
+            .map(|pulls| pulls.split(',').map(Pull::from_str).collect())
+            .collect::<Result<_>>()?;
+
+        Ok(Game {
+            id: id.parse()?,
+            pulls,
+        })
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Display for Bag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "empty");
+        }
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|(color, count)| format!("{} {}", count, color))
+                .join(", ")
+        )
+    }
+}
+
+impl Display for Pull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.0, self.1)
+    }
+}
+
+/// Writes a game in its canonical form, `Game 1: 3 blue, 4 red; 2 green`.
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Game {}: {}",
+            self.id,
+            self.pulls
+                .iter()
+                .map(|pull_set| pull_set.iter().join(", "))
+                .join("; ")
+        )
+    }
+}
+
+/// Rewrites every game in canonical form, its rounds' colors sorted and
+/// merged.
+pub fn normalize(input: &str) -> Result<String> {
+    Game::new_from_list(input)?
+        .iter()
+        .map(|game| Ok(format!("{}\n", game.normalized()?)))
+        .collect()
+}
+
+/// Generates `size` games of up to six rounds each.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut colors = ["red", "green", "blue"];
+    let mut output = String::new();
+    for id in 1..=size {
+        let rounds = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                colors.shuffle(rng);
+                let shown = rng.gen_range(1..=colors.len());
+                colors[..shown]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .join(", ")
+            })
+            .join("; ");
+        output.push_str(&format!("Game {}: {}\n", id, rounds));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        days::{Extra, Trace},
+        util,
+    };
+    extern crate test;
+
+    use proptest::prelude::*;
+    use test::Bencher;
+
+    fn arb_pull() -> impl Strategy<Value = Pull> {
+        (0..100usize, "[a-z][a-z_-]{0,7}").prop_map(|(count, color)| Pull(count, Color(color)))
+    }
+
+    pub(super) const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn test_parts() {
+        let games = Game::new_from_list(EXAMPLE).unwrap();
+        let bag = Bag::default();
+        assert_eq!(part_1(&games, &bag).unwrap(), 8);
+        assert_eq!(part_2(&games, &bag).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_power_overflow() {
+        let bag = Bag::default();
+        let games = Game::new_from_list(
+            "Game 1: 4294967296 red, 4294967296 green, 1 blue
+",
+        )
+        .unwrap();
+        assert_eq!(
+            part_2(&games, &bag).unwrap_err().to_string(),
+            "power of game 1 overflowed"
+        );
+        // a color never shown makes the power zero however large the rest
+        let games = Game::new_from_list(
+            "Game 1: 4294967296 red, 4294967296 green
+",
+        )
+        .unwrap();
+        assert_eq!(part_2(&games, &bag).unwrap(), 0);
+
+        let games = Game::new_from_list(
+            "Game 1: 4294967296 red, 2147483648 green, 1 blue
+Game 2: 4294967296 red, 2147483648 green, 1 blue
+",
+        )
+        .unwrap();
+        assert_eq!(
+            part_2(&games, &bag).unwrap_err().to_string(),
+            "sum of powers overflowed"
+        );
+
+        let games =
+            Game::new_from_list("Game 18446744073709551615: 1 red\nGame 1: 1 red\n").unwrap();
+        assert_eq!(
+            part_1(&games, &bag).unwrap_err().to_string(),
+            "sum of possible game ids overflowed"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let ctx = Context {
+            trace: Trace::enabled(),
+            ..Context::default()
+        };
+        solve(EXAMPLE, &ctx).unwrap();
+        let lines = ctx.trace.into_lines();
+        let messages = |part: u8| -> Vec<&str> {
+            lines
+                .iter()
+                .filter(|line| line.part == part)
+                .map(|line| line.message.as_str())
+                .collect()
+        };
+        assert_eq!(
+            messages(1),
+            [
+                "game 1: possible",
+                "game 2: possible",
+                "game 3: impossible, round 1 pulled 20 red, 8 more than the bag's 12",
+                "game 4: impossible, round 3 pulled 15 blue, 1 more than the bag's 14, round 3 pulled 14 red, 2 more than the bag's 12",
+                "game 5: possible",
+            ]
+        );
+        assert_eq!(
+            messages(2)[0],
+            "game 1: minimal bag 6 blue, 2 green, 4 red, power 48"
+        );
+    }
+
+    #[test]
+    fn test_feasibility_report() {
+        let params = crate::config::Params::default();
+        let text = report(EXAMPLE, &params, Format::Text).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "game 1: possible, minimal bag 6 blue, 2 green, 4 red, power 48"
+        );
+        assert_eq!(
+            lines[2..4],
+            [
+                "game 3: impossible, minimal bag 6 blue, 13 green, 20 red, power 1560",
+                "  round 1 pulled 20 red, 8 more than the bag's 12",
+            ]
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report(EXAMPLE, &params, Format::Json).unwrap()).unwrap();
+        assert_eq!(json.as_array().unwrap().len(), 5);
+        assert_eq!(
+            json[3]["violations"][1],
+            serde_json::json!({ "round": 3, "color": "red", "count": 14, "limit": 12, "excess": 2 })
+        );
+        assert_eq!(json[3]["minimal_bag"]["blue"], 15);
+        assert_eq!(json[3]["possible"], false);
+    }
+
+    #[test]
+    fn test_estimate_notes() {
+        let config =
+            crate::config::Config::parse("[days.2023.day02]\nestimate = true\nbag_size = 20\n")
+                .unwrap();
+        let ctx = Context {
+            params: config.params(2023, 2),
+            trace: Trace::enabled(),
+            ..Context::default()
+        };
+        let answers = solve(EXAMPLE, &ctx).unwrap();
+        let lines = ctx.trace.into_lines();
+        assert_eq!(lines.len(), 15);
+        assert!(lines[2]
+            .message
+            .starts_with("game 1: most likely bag 10 blue, 4 green, 6 red, likelihood "));
+        // game 3 shows 20 red and 13 green
+        assert_eq!(lines[8].message, "game 3: no bag of 20 cubes explains it");
+
+        let extras: Vec<String> = answers.extras.iter().map(Extra::to_string).collect();
+        assert_eq!(extras.len(), 5);
+        assert!(extras[0].starts_with("game 1 estimate: most likely bag 10 blue, 4 green, 6 red"));
+        assert!(extras[0].ends_with("for the configured bag"));
+        assert_eq!(
+            extras[2],
+            "game 3 estimate: error: no bag of 20 cubes explains it"
+        );
+
+        let params = config.params(2023, 2);
+        let text = report(EXAMPLE, &params, Format::Text).unwrap();
+        assert!(text
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("  most likely bag 10 blue, 4 green, 6 red, likelihood "));
+        assert!(text.contains("\n  no estimate, no bag of 20 cubes explains it\n"));
+        let json: serde_json::Value =
+            serde_json::from_str(&report(EXAMPLE, &params, Format::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["estimate"]["bag"]["blue"], 10);
+        assert!(json[0]["estimate"]["likelihood"].as_f64().unwrap() > 0.0);
+        assert_eq!(json[2]["estimate_error"], "no bag of 20 cubes explains it");
+        assert!(json[2].get("estimate").is_none());
+    }
+
+    #[test]
+    fn test_query_answer() {
+        let config = crate::config::Config::parse(
+            "[days.2023.day02]\nquery = \"sum 18446744073709551615 where id < 3\"\n",
+        )
+        .unwrap();
+        let ctx = Context {
+            params: config.params(2023, 2),
+            ..Context::default()
+        };
+        let answers = solve(EXAMPLE, &ctx).unwrap();
+        assert_eq!(answers.part_2.as_deref(), Some("2286"));
+        assert_eq!(
+            answers.extras[0].to_string(),
+            "query `sum 18446744073709551615 where id < 3`: error: a sum overflowed"
+        );
+
+        let config =
+            crate::config::Config::parse("[days.2023.day02]\nquery = \"sum power\"\n").unwrap();
+        let ctx = Context {
+            params: config.params(2023, 2),
+            ..Context::default()
+        };
+        let answers = solve(EXAMPLE, &ctx).unwrap();
+        assert_eq!(answers.extras[0].to_string(), "query `sum power`: 2286");
+    }
+
+    #[test]
+    fn test_open_colors() {
+        let games = Game::new_from_list("Game 1: 2 teal, 1 red; 3 teal\nGame 2: 1 red\n").unwrap();
+        let config = crate::config::Config::parse(
+            "[days.2023.day02]\nbag = { teal = 3, red = 1 }\nmax_red = 2\n",
+        )
+        .unwrap();
+        let bag = Bag::from_params(&config.params(2023, 2)).unwrap();
+        assert_eq!(bag.count(&Color(String::from("red"))), 2);
+        assert_eq!(part_1(&games, &bag).unwrap(), 3);
+        // game 2 never shows teal
+        assert_eq!(part_2(&games, &bag).unwrap(), 3);
+
+        // colors missing from the bag can't be pulled at all
+        let games = Game::new_from_list("Game 1: 1 ochre\n").unwrap();
+        assert_eq!(part_1(&games, &Bag::default()).unwrap(), 0);
+        assert!("Game 1: 1 red, 2 bl ue".parse::<Game>().is_err());
+    }
+
+    fn arb_game() -> impl Strategy<Value = Game> {
+        let pull_set = prop::collection::vec(arb_pull(), 1..4);
+        (1..1000usize, prop::collection::vec(pull_set, 1..7))
+            .prop_map(|(id, pulls)| Game { id, pulls })
+    }
+
+    proptest! {
+        #[test]
+        fn test_game_round_trip(game in arb_game()) {
+            let rendered = game.to_string();
+            let parsed: Game = rendered.parse().unwrap();
+            prop_assert_eq!(parsed.to_string(), rendered);
+            prop_assert_eq!(parsed, game);
+        }
+
+        #[test]
+        fn test_normalize_is_stable(game in arb_game()) {
+            let normalized = normalize(&game.to_string()).unwrap();
+            prop_assert_eq!(normalize(&normalized).unwrap(), normalized.clone());
+            let game = game.normalized().unwrap();
+            prop_assert_eq!(normalized.trim_end().parse::<Game>().unwrap(), game);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let input = "Game 1:3 red,2 blue ;  1 red, 4 red, 2 green\nGame  12 : 7 teal\n";
+        assert_eq!(
+            normalize(input).unwrap(),
+            "Game 1: 2 blue, 3 red; 2 green, 5 red\nGame 12: 7 teal\n"
+        );
+        assert!(normalize("Round 1: 3 red\n").is_err());
+        assert_eq!(
+            normalize("Game 1: 18446744073709551615 red, 1 red\n")
+                .unwrap_err()
+                .to_string(),
+            "game 1 round 1 shows too many red"
+        );
+        assert!(normalize("Game 1: 3 red 4\n").is_err());
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 2);
+        b.iter(|| part_1(&Game::new_from_list(&input).unwrap(), &Bag::default()));
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 2);
+        b.iter(|| part_2(&Game::new_from_list(&input).unwrap(), &Bag::default()));
+    }
+}
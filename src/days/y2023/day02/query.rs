@@ -0,0 +1,477 @@
+//! A small language for asking ad hoc questions about the games, e.g.
+//!
+//! ```text
+//! list where max(blue) > 10 and all(red <= 3)
+//! sum id where power > 500
+//! count where rounds >= 4 or not any(green == 0)
+//! ```
+//!
+//! A query is `count`, `list` or `sum <value>`, optionally followed by
+//! `where` and a condition on each game. Values are numbers, a game's `id`,
+//! `power` and `rounds`, and `max`, `min` or `sum` of a color over its
+//! rounds. Inside `any(...)` or `all(...)`, which hold for some or every
+//! round, a bare color is how many cubes of it that round showed. A round
+//! that doesn't show a color showed none of it. Conditions compare values
+//! with `<`, `<=`, `>`, `>=`, `==` or `!=` and combine with `and`, `or`,
+//! `not` and parentheses.
+
+use std::{fmt::Display, iter::Peekable, str::FromStr, vec::IntoIter};
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use super::{Bag, Color, Game, Pull};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Query {
+    output: Output,
+    filter: Option<Condition>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Output {
+    Count,
+    List,
+    Sum(Value),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Condition {
+    Compare(Value, Op, Value),
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    /// Holds for some round.
+    Any(Box<Condition>),
+    /// Holds for every round.
+    All(Box<Condition>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    Number(usize),
+    Id,
+    Power,
+    Rounds,
+    Aggregate(Aggregate, Color),
+    /// Cubes of the color in the round being looked at.
+    Shown(Color),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggregate {
+    Max,
+    Min,
+    Sum,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// What a query found.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Answer {
+    Number(usize),
+    Ids(Vec<usize>),
+}
+
+impl Query {
+    /// Runs the query over `games`, with `bag` giving the colors a game's
+    /// power is taken over. Fails if a value outgrows a `usize`.
+    pub(super) fn run(&self, games: &[Game], bag: &Bag) -> Result<Answer> {
+        let mut matching = Vec::new();
+        for game in games {
+            if self
+                .filter
+                .as_ref()
+                .map_or(Ok(true), |filter| filter.holds(game, bag, None))?
+            {
+                matching.push(game);
+            }
+        }
+        Ok(match &self.output {
+            Output::Count => Answer::Number(matching.len()),
+            Output::List => Answer::Ids(matching.iter().map(|game| game.id).collect()),
+            Output::Sum(value) => {
+                Answer::Number(sum(matching.iter().map(|game| value.of(game, bag, None)))?)
+            }
+        })
+    }
+}
+
+impl Condition {
+    fn holds(&self, game: &Game, bag: &Bag, round: Option<&[Pull]>) -> Result<bool> {
+        Ok(match self {
+            Condition::Compare(left, op, right) => {
+                let (left, right) = (left.of(game, bag, round)?, right.of(game, bag, round)?);
+                match op {
+                    Op::Lt => left < right,
+                    Op::Le => left <= right,
+                    Op::Gt => left > right,
+                    Op::Ge => left >= right,
+                    Op::Eq => left == right,
+                    Op::Ne => left != right,
+                }
+            }
+            Condition::Not(condition) => !condition.holds(game, bag, round)?,
+            Condition::And(left, right) => {
+                left.holds(game, bag, round)? && right.holds(game, bag, round)?
+            }
+            Condition::Or(left, right) => {
+                left.holds(game, bag, round)? || right.holds(game, bag, round)?
+            }
+            Condition::Any(condition) => game
+                .pulls
+                .iter()
+                .map(|pulls| condition.holds(game, bag, Some(pulls)))
+                .process_results(|mut holds| holds.any(|holds| holds))?,
+            Condition::All(condition) => game
+                .pulls
+                .iter()
+                .map(|pulls| condition.holds(game, bag, Some(pulls)))
+                .process_results(|mut holds| holds.all(|holds| holds))?,
+        })
+    }
+}
+
+impl Value {
+    fn of(&self, game: &Game, bag: &Bag, round: Option<&[Pull]>) -> Result<usize> {
+        Ok(match self {
+            Value::Number(n) => *n,
+            Value::Id => game.id,
+            Value::Power => game.power(bag)?,
+            Value::Rounds => game.pulls.len(),
+            Value::Aggregate(aggregate, color) => {
+                let shown = game.pulls.iter().map(|pulls| shown(pulls, color));
+                match aggregate {
+                    Aggregate::Max => shown.process_results(|shown| shown.max())?.unwrap_or(0),
+                    Aggregate::Min => shown.process_results(|shown| shown.min())?.unwrap_or(0),
+                    Aggregate::Sum => sum(shown)?,
+                }
+            }
+            Value::Shown(color) => shown(round.expect("checked when parsed"), color)?,
+        })
+    }
+}
+
+/// Cubes of `color` shown in a round.
+fn shown(pulls: &[Pull], color: &Color) -> Result<usize> {
+    sum(pulls
+        .iter()
+        .filter(|pull| pull.1 == *color)
+        .map(|pull| Ok(pull.0)))
+}
+
+fn sum(values: impl IntoIterator<Item = Result<usize>>) -> Result<usize> {
+    values.into_iter().try_fold(0usize, |sum, value| {
+        sum.checked_add(value?)
+            .ok_or_else(|| anyhow!("a sum overflowed"))
+    })
+}
+
+impl FromStr for Query {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(value)?.into_iter().peekable(),
+            in_round: false,
+        };
+        let output = match parser.word()?.as_str() {
+            "count" => Output::Count,
+            "list" => Output::List,
+            "sum" => Output::Sum(parser.value()?),
+            word => bail!("expected `count`, `list` or `sum`, got `{}`", word),
+        };
+        let filter = match parser.tokens.next() {
+            None => None,
+            Some(Token::Word(word)) if word == "where" => Some(parser.condition()?),
+            Some(token) => bail!("expected `where`, got `{}`", token),
+        };
+        if let Some(token) = parser.tokens.next() {
+            bail!("unexpected `{}` after the query", token);
+        }
+        Ok(Query { output, filter })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Number(usize),
+    Op(Op),
+    Open,
+    Close,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "{}", word),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Op(op) => write!(f, "{}", op),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '<' | '>' | '=' | '!' => {
+                let equals = chars.next_if(|&(_, c)| c == '=').is_some();
+                Token::Op(match (c, equals) {
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    ('=', true) => Op::Eq,
+                    ('!', true) => Op::Ne,
+                    _ => bail!("unknown operator `{}` at {}", c, start),
+                })
+            }
+            c if is_word(c) => {
+                let mut end = start + c.len_utf8();
+                while let Some((at, c)) = chars.next_if(|&(_, c)| is_word(c)) {
+                    end = at + c.len_utf8();
+                }
+                let word = &query[start..end];
+                match word.parse() {
+                    Ok(n) => Token::Number(n),
+                    Err(_) if c.is_ascii_digit() => bail!("invalid number `{}`", word),
+                    Err(_) => Token::Word(word.to_owned()),
+                }
+            }
+            _ => bail!("unexpected `{}` at {}", c, start),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// The characters colors and keywords are made of, as in a pull's color.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// A recursive descent parser, one function per level of precedence from
+/// `or`, the loosest, down to single comparisons.
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+    /// Whether the parser is inside `any` or `all`, where bare colors mean
+    /// something.
+    in_round: bool,
+}
+
+impl Parser {
+    fn condition(&mut self) -> Result<Condition> {
+        let mut condition = self.conjunction()?;
+        while self.keyword("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.conjunction()?));
+        }
+        Ok(condition)
+    }
+
+    fn conjunction(&mut self) -> Result<Condition> {
+        let mut condition = self.negation()?;
+        while self.keyword("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.negation()?));
+        }
+        Ok(condition)
+    }
+
+    fn negation(&mut self) -> Result<Condition> {
+        if self.keyword("not") {
+            return Ok(Condition::Not(Box::new(self.negation()?)));
+        }
+        if self.tokens.next_if_eq(&Token::Open).is_some() {
+            let condition = self.condition()?;
+            self.expect(Token::Close)?;
+            return Ok(condition);
+        }
+        for (keyword, quantifier) in [
+            ("any", Condition::Any as fn(_) -> _),
+            ("all", Condition::All),
+        ] {
+            if self.keyword(keyword) {
+                if self.in_round {
+                    bail!("`{}` can't be used inside another `any` or `all`", keyword);
+                }
+                self.expect(Token::Open)?;
+                self.in_round = true;
+                let condition = self.condition()?;
+                self.in_round = false;
+                self.expect(Token::Close)?;
+                return Ok(quantifier(Box::new(condition)));
+            }
+        }
+
+        let left = self.value()?;
+        let op = match self.tokens.next() {
+            Some(Token::Op(op)) => op,
+            Some(token) => bail!("expected a comparison, got `{}`", token),
+            None => bail!("expected a comparison, got the end of the query"),
+        };
+        Ok(Condition::Compare(left, op, self.value()?))
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        if let Some(Token::Number(n)) = self.tokens.peek() {
+            let n = *n;
+            self.tokens.next();
+            return Ok(Value::Number(n));
+        }
+        let word = self.word()?;
+        let aggregate = match word.as_str() {
+            "id" => return Ok(Value::Id),
+            "power" => return Ok(Value::Power),
+            "rounds" => return Ok(Value::Rounds),
+            "max" => Aggregate::Max,
+            "min" => Aggregate::Min,
+            "sum" => Aggregate::Sum,
+            _ if self.in_round => return Ok(Value::Shown(word.parse()?)),
+            _ => bail!(
+                "unknown value `{}`, colors on their own are only counted inside `any` or `all`",
+                word
+            ),
+        };
+        self.expect(Token::Open)?;
+        let color = self.word()?.parse()?;
+        self.expect(Token::Close)?;
+        Ok(Value::Aggregate(aggregate, color))
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.tokens.next() {
+            Some(Token::Word(word)) => Ok(word),
+            Some(token) => bail!("expected a word, got `{}`", token),
+            None => bail!("expected a word, got the end of the query"),
+        }
+    }
+
+    /// Consumes `keyword` if it comes next.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        self.tokens
+            .next_if(|token| matches!(token, Token::Word(word) if word == keyword))
+            .is_some()
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.tokens.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => bail!("expected `{}`, got `{}`", expected, token),
+            None => Err(anyhow!("expected `{}`, got the end of the query", expected)),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Ids(ids) => write!(f, "{}", ids.iter().join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::y2023::day02::tests::EXAMPLE;
+
+    fn run(query: &str) -> Answer {
+        let games = Game::new_from_list(EXAMPLE).unwrap();
+        query
+            .parse::<Query>()
+            .unwrap()
+            .run(&games, &Bag::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_queries() {
+        assert_eq!(run("count"), Answer::Number(5));
+        // part 1 and part 2
+        assert_eq!(
+            run("sum id where max(red) <= 12 and max(green) <= 13 and max(blue) <= 14"),
+            Answer::Number(8)
+        );
+        assert_eq!(run("sum power"), Answer::Number(2286));
+        assert_eq!(run("list where power > 500"), Answer::Ids(vec![3, 4]));
+        assert_eq!(
+            run("list where any(blue > 5) and not all(red >= 1)"),
+            Answer::Ids(vec![1])
+        );
+        assert_eq!(
+            run("list where min(green) == 0 or (rounds == 2 and id != 2)"),
+            Answer::Ids(vec![1, 5])
+        );
+        assert_eq!(run("sum sum(red) where id < 3"), Answer::Number(6));
+    }
+
+    #[test]
+    fn test_query_overflow() {
+        let games = Game::new_from_list(
+            "Game 18446744073709551615: 1 red\nGame 1: 18446744073709551615 red; 1 red, 0 red",
+        )
+        .unwrap();
+        for query in [
+            "sum id",
+            "count where sum(red) > 1",
+            "sum 18446744073709551615",
+        ] {
+            let query: Query = query.parse().unwrap();
+            assert!(query.run(&games, &Bag::default()).is_err(), "{:?}", query);
+        }
+        let query: Query = "count where max(red) > 1".parse().unwrap();
+        assert_eq!(
+            query.run(&games, &Bag::default()).unwrap(),
+            Answer::Number(1)
+        );
+    }
+
+    #[test]
+    fn test_query_errors() {
+        for query in [
+            "",
+            "average id",
+            "count where blue > 3",
+            "count where any(any(blue > 3))",
+            "count where max(blue) >",
+            "count where (rounds > 1",
+            "count where rounds = 1",
+            "count where rounds > 1 1",
+            "count where rounds > 12x",
+            "count where max(bl.ue) > 1",
+        ] {
+            assert!(query.parse::<Query>().is_err(), "{:?}", query);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! A game by game account of both parts, for checking them against the
+//! input by eye: the pulls that make a game impossible, by how much they
+//! exceed the bag, and the minimal bag behind the game's power. When
+//! estimating, also the most likely bag behind the game.
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::fmt::Display;
+
+use super::{
+    estimate::{self, Weighing},
+    Bag, Game, Violation,
+};
+
+#[derive(Debug, Serialize)]
+pub(super) struct GameReport<'a> {
+    id: usize,
+    possible: bool,
+    violations: Vec<Violation<'a>>,
+    minimal_bag: Bag,
+    /// `None` when the power overflows.
+    power: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<Weighing>,
+    /// Why there is no estimate when one was asked for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate_error: Option<String>,
+}
+
+impl<'a> GameReport<'a> {
+    /// Estimates the bag of `estimate_size` cubes behind the game too, if
+    /// given.
+    pub(super) fn new(game: &'a Game, bag: &Bag, estimate_size: Option<usize>) -> Self {
+        let violations = game.violations(bag);
+        let (estimate, estimate_error) =
+            match estimate_size.map(|size| estimate::weigh(game, bag, size)) {
+                Some(Ok(weighing)) => (Some(weighing), None),
+                Some(Err(e)) => (None, Some(format!("{:#}", e))),
+                None => (None, None),
+            };
+        GameReport {
+            id: game.id,
+            possible: violations.is_empty(),
+            violations,
+            minimal_bag: game.minimal_bag(),
+            power: game.power(bag).ok(),
+            estimate,
+            estimate_error,
+        }
+    }
+}
+
+impl Serialize for Violation<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut violation = serializer.serialize_struct("Violation", 5)?;
+        violation.serialize_field("round", &self.round)?;
+        violation.serialize_field("color", &self.pull.1)?;
+        violation.serialize_field("count", &self.pull.0)?;
+        violation.serialize_field("limit", &self.limit)?;
+        violation.serialize_field("excess", &(self.pull.0 - self.limit))?;
+        violation.end()
+    }
+}
+
+/// Writes the game's verdict and minimal bag on one line, followed by an
+/// indented line for each violation and one for the estimate.
+impl Display for GameReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = if self.possible {
+            "possible"
+        } else {
+            "impossible"
+        };
+        write!(
+            f,
+            "game {}: {}, minimal bag {}, ",
+            self.id, verdict, self.minimal_bag
+        )?;
+        match self.power {
+            Some(power) => writeln!(f, "power {}", power)?,
+            None => writeln!(f, "power overflows")?,
+        }
+        for violation in self.violations.iter() {
+            writeln!(f, "  {}", violation)?;
+        }
+        if let Some(estimate) = &self.estimate {
+            writeln!(f, "  {}", estimate)?;
+        }
+        if let Some(error) = &self.estimate_error {
+            writeln!(f, "  no estimate, {}", error)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,571 @@
+use std::fmt::Display;
+
+use crate::{
+    days::{Answers, Context},
+    viz::{Grid, Rgb},
+};
+use anyhow::{anyhow, bail, Context as _, Result};
+use rand::{rngs::StdRng, Rng};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Component {
+    PartNumber(usize),
+    Symbol(char),
+}
+
+/// A component and the cells it covers, all in one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    component: Component,
+    row: usize,
+    /// The first and last column covered.
+    start: usize,
+    end: usize,
+}
+
+/// The engine schematic as a dense grid of cells, each holding the id of
+/// the component covering it, so what is next to a component is found by
+/// looking at the cells around it.
+#[derive(Debug)]
+struct Schematic {
+    width: usize,
+    height: usize,
+    cells: Vec<Option<usize>>,
+    /// Every component, indexed by id, in reading order.
+    components: Vec<Placement>,
+}
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let schematic = Schematic::new(input)?;
+    // each rule is read on its own, so one bad rule doesn't hide the others
+    let rules: Vec<toml::Value> = ctx.params.get_or("rules", Vec::new())?;
+    for part in 1..=2 {
+        ctx.frames.record(part, || draw(&schematic, part));
+    }
+    let mut answers = Answers::default()
+        .with_part_1(part_1(&schematic)?)
+        .with_part_2(part_2(&schematic)?);
+    for (n, rule) in rules.into_iter().enumerate() {
+        answers = match rule.try_into::<Rule>() {
+            Ok(rule) => answers.with_extra(
+                format!("rule {}", rule),
+                rule.validate().and_then(|_| rule.total(&schematic)),
+            ),
+            Err(e) => answers.with_extra(
+                format!("rule {}", n + 1),
+                Err::<usize, _>(anyhow!(e).context("invalid rule")),
+            ),
+        };
+    }
+    Ok(answers)
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    Schematic::new(input).map(drop)
+}
+
+fn part_1(schematic: &Schematic) -> Result<usize> {
+    schematic
+        .components
+        .iter()
+        .enumerate()
+        .filter_map(|(id, placement)| match placement.component {
+            Component::PartNumber(n) if schematic.next_to_symbol(id) => Some(n),
+            _ => None,
+        })
+        .try_fold(0usize, |sum, n| {
+            sum.checked_add(n)
+                .ok_or_else(|| anyhow!("sum of part numbers overflowed"))
+        })
+}
+
+fn part_2(schematic: &Schematic) -> Result<usize> {
+    GEAR.total(schematic).context("sum of gear ratios")
+}
+
+/// A gear is a `*` next to exactly two part numbers, and its ratio their
+/// product.
+const GEAR: Rule = Rule {
+    symbol: Some('*'),
+    neighbours: Some(2),
+    min_neighbours: None,
+    max_neighbours: None,
+    combine: Combine::Product,
+};
+
+/// Which symbols count, by what is next to them, and what they are worth:
+/// their neighbouring part numbers summed or multiplied together. Rules are
+/// read from the `rules` parameter, e.g.
+/// `rules = [{ symbol = "#", neighbours = 3, combine = "sum" }]`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    /// The symbol the rule is about, or any symbol if not given.
+    symbol: Option<char>,
+    /// Exactly how many part numbers must be next to the symbol.
+    neighbours: Option<usize>,
+    min_neighbours: Option<usize>,
+    max_neighbours: Option<usize>,
+    combine: Combine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Combine {
+    Sum,
+    Product,
+}
+
+impl Rule {
+    fn validate(&self) -> Result<()> {
+        if self.neighbours.is_some()
+            && (self.min_neighbours.is_some() || self.max_neighbours.is_some())
+        {
+            bail!("gives both an exact and a ranged neighbour count");
+        }
+        Ok(())
+    }
+
+    fn applies_to(&self, symbol: char, neighbours: usize) -> bool {
+        self.symbol.is_none_or(|s| s == symbol)
+            && self.neighbours.is_none_or(|n| neighbours == n)
+            && self.min_neighbours.is_none_or(|min| neighbours >= min)
+            && self.max_neighbours.is_none_or(|max| neighbours <= max)
+    }
+
+    /// What the symbol with `numbers` around it is worth, if that fits.
+    fn value(&self, numbers: &[usize]) -> Option<usize> {
+        match self.combine {
+            Combine::Sum => numbers.iter().try_fold(0usize, |a, &b| a.checked_add(b)),
+            Combine::Product => numbers.iter().try_fold(1usize, |a, &b| a.checked_mul(b)),
+        }
+    }
+
+    /// The sum of the values of every symbol the rule applies to.
+    fn total(&self, schematic: &Schematic) -> Result<usize> {
+        schematic
+            .matching(self)
+            .try_fold(0usize, |total, (_, numbers)| {
+                self.value(&numbers)
+                    .and_then(|value| total.checked_add(value))
+            })
+            .ok_or_else(|| anyhow!("total overflowed"))
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.symbol {
+            Some(symbol) => write!(f, "`{}`", symbol)?,
+            None => write!(f, "any symbol")?,
+        }
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        match (self.neighbours, self.min_neighbours, self.max_neighbours) {
+            (Some(n), _, _) => write!(f, " with {} neighbour{}", n, plural(n))?,
+            (None, Some(min), Some(max)) => write!(f, " with {} to {} neighbours", min, max)?,
+            (None, Some(min), None) => {
+                write!(f, " with at least {} neighbour{}", min, plural(min))?
+            }
+            (None, None, Some(max)) => write!(f, " with at most {} neighbour{}", max, plural(max))?,
+            (None, None, None) => (),
+        }
+        match self.combine {
+            Combine::Sum => write!(f, ", summed"),
+            Combine::Product => write!(f, ", multiplied"),
+        }
+    }
+}
+
+/// One piece of a schematic row. Columns count characters from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// A run of digits covering `start..=end`.
+    Number {
+        value: usize,
+        start: usize,
+        end: usize,
+    },
+    Symbol {
+        symbol: char,
+        col: usize,
+    },
+    Empty {
+        col: usize,
+    },
+}
+
+impl Token {
+    /// The last column the token covers.
+    fn end(&self) -> usize {
+        match *self {
+            Token::Number { end, .. } => end,
+            Token::Symbol { col, .. } | Token::Empty { col } => col,
+        }
+    }
+}
+
+/// Splits row `row` of a schematic into tokens. Anything other than a
+/// digit or `.` is a symbol, whatever the script, except whitespace,
+/// control characters and digits outside ASCII, which are errors.
+fn lex(row: usize, line: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().enumerate().peekable();
+    while let Some((col, c)) = chars.next() {
+        let token = match c {
+            '.' => Token::Empty { col },
+            '0'..='9' => {
+                let mut digits = String::from(c);
+                let mut end = col;
+                while let Some((at, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    digits.push(digit);
+                    end = at;
+                }
+                let value = digits.parse().with_context(|| {
+                    format!("part number at row {}, column {}", row + 1, col + 1)
+                })?;
+                Token::Number {
+                    value,
+                    start: col,
+                    end,
+                }
+            }
+            c if c.is_whitespace() || c.is_control() || c.is_numeric() => bail!(
+                "unexpected character `{}` at row {}, column {}",
+                c.escape_debug(),
+                row + 1,
+                col + 1
+            ),
+            symbol => Token::Symbol { symbol, col },
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+impl Schematic {
+    fn new(input: &str) -> Result<Self> {
+        let rows: Vec<Vec<Token>> = input
+            .lines()
+            .enumerate()
+            .map(|(row, line)| lex(row, line))
+            .collect::<Result<_>>()?;
+        let height = rows.len();
+        let width = rows
+            .iter()
+            .filter_map(|tokens| Some(tokens.last()?.end() + 1))
+            .max()
+            .unwrap_or(0);
+        let mut schematic = Schematic {
+            width,
+            height,
+            cells: vec![None; width * height],
+            components: Vec::new(),
+        };
+
+        for (row, tokens) in rows.into_iter().enumerate() {
+            for token in tokens {
+                let (component, start, end) = match token {
+                    Token::Number { value, start, end } => {
+                        (Component::PartNumber(value), start, end)
+                    }
+                    Token::Symbol { symbol, col } => (Component::Symbol(symbol), col, col),
+                    Token::Empty { .. } => continue,
+                };
+                let id = schematic.components.len();
+                schematic.components.push(Placement {
+                    component,
+                    row,
+                    start,
+                    end,
+                });
+                for col in start..=end {
+                    schematic.cells[row * width + col] = Some(id);
+                }
+            }
+        }
+        Ok(schematic)
+    }
+
+    /// The ids of the components in the cells surrounding component `id`,
+    /// each once, in reading order.
+    fn neighbours(&self, id: usize) -> Vec<usize> {
+        let placement = &self.components[id];
+        let rows = placement.row.saturating_sub(1)..=(placement.row + 1).min(self.height - 1);
+        let cols = placement.start.saturating_sub(1)..=(placement.end + 1).min(self.width - 1);
+        let mut neighbours: Vec<usize> = rows
+            .flat_map(|row| cols.clone().map(move |col| (row, col)))
+            .filter_map(|(row, col)| self.cells[row * self.width + col])
+            .filter(|&other| other != id)
+            .collect();
+        // a component covers a run of cells in one row, so its repeats are
+        // already next to each other
+        neighbours.dedup();
+        neighbours
+    }
+
+    fn next_to_symbol(&self, id: usize) -> bool {
+        self.neighbours(id)
+            .into_iter()
+            .any(|other| matches!(self.components[other].component, Component::Symbol(_)))
+    }
+
+    /// The part numbers next to component `id`.
+    fn part_numbers_around(&self, id: usize) -> Vec<usize> {
+        self.neighbours(id)
+            .into_iter()
+            .filter_map(|other| match self.components[other].component {
+                Component::PartNumber(n) => Some(n),
+                Component::Symbol(_) => None,
+            })
+            .collect()
+    }
+
+    /// Every symbol `rule` applies to, by id and with the part numbers next
+    /// to it.
+    fn matching<'a>(&'a self, rule: &'a Rule) -> impl Iterator<Item = (usize, Vec<usize>)> + 'a {
+        self.components
+            .iter()
+            .enumerate()
+            .filter_map(move |(id, placement)| {
+                let Component::Symbol(symbol) = placement.component else {
+                    return None;
+                };
+                let numbers = self.part_numbers_around(id);
+                rule.applies_to(symbol, numbers.len())
+                    .then_some((id, numbers))
+            })
+    }
+}
+
+/// Colours the schematic's symbols, and the part numbers that count towards
+/// `part`'s answer.
+fn draw(schematic: &Schematic, part: u8) -> Grid {
+    let mut grid = Grid::new(schematic.width, schematic.height, Rgb::BLACK);
+    let gears: Vec<usize> = schematic.matching(&GEAR).map(|(id, _)| id).collect();
+    for (id, placement) in schematic.components.iter().enumerate() {
+        let color = match placement.component {
+            Component::Symbol(_) if part == 2 && gears.contains(&id) => Rgb::RED,
+            Component::Symbol(_) => Rgb::YELLOW,
+            Component::PartNumber(_) => {
+                let counts = match part {
+                    1 => schematic.next_to_symbol(id),
+                    _ => schematic
+                        .neighbours(id)
+                        .iter()
+                        .any(|other| gears.contains(other)),
+                };
+                if counts {
+                    Rgb::GREEN
+                } else {
+                    Rgb::GREY
+                }
+            }
+        };
+        for col in placement.start..=placement.end {
+            grid.set(placement.row, col, color);
+        }
+    }
+    grid
+}
+
+const SYMBOLS: [char; 9] = ['*', '#', '+', '$', '/', '@', '=', '%', '&'];
+
+/// Generates a `size` by `size` schematic scattered with part numbers and
+/// symbols.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let mut col = 0;
+        while col < size {
+            let roll = rng.gen_range(0..10);
+            let len = rng.gen_range(1..=3);
+            if roll < 2 && col + len < size {
+                let num = rng.gen_range(10usize.pow(len as u32 - 1)..10usize.pow(len as u32));
+                output.push_str(&num.to_string());
+                // keep neighbouring numbers apart so they don't merge
+                output.push('.');
+                col += len + 1;
+            } else if roll < 3 {
+                output.push(SYMBOLS[rng.gen_range(0..SYMBOLS.len())]);
+                col += 1;
+            } else {
+                output.push('.');
+                col += 1;
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::Extra, util};
+    extern crate test;
+
+    use test::Bencher;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_part_1() {
+        let schematic = Schematic::new(EXAMPLE).unwrap();
+        assert_eq!(part_1(&schematic).unwrap(), 4361);
+
+        let schematic = Schematic::new("9999999999999999999*9999999999999999999\n").unwrap();
+        assert_eq!(
+            part_1(&schematic).unwrap_err().to_string(),
+            "sum of part numbers overflowed"
+        );
+    }
+
+    #[test]
+    fn test_part_2() {
+        let schematic = Schematic::new(EXAMPLE).unwrap();
+        assert_eq!(part_2(&schematic).unwrap(), 467835);
+    }
+
+    #[test]
+    fn test_rules() {
+        let config = crate::config::Config::parse(
+            r##"
+[days.2023.day03]
+rules = [
+    { symbol = "#", neighbours = 1, combine = "sum" },
+    { min_neighbours = 2, combine = "product" },
+    { symbol = "*", max_neighbours = 1, combine = "sum" },
+]
+"##,
+        )
+        .unwrap();
+        let ctx = Context {
+            params: config.params(2023, 3),
+            ..Context::default()
+        };
+        let answers = solve(EXAMPLE, &ctx).unwrap();
+        assert_eq!(answers.part(2), Some("467835"));
+        let extras: Vec<String> = answers.extras.iter().map(Extra::to_string).collect();
+        assert_eq!(
+            extras,
+            [
+                "rule `#` with 1 neighbour, summed: 633",
+                "rule any symbol with at least 2 neighbours, multiplied: 467835",
+                "rule `*` with at most 1 neighbour, summed: 617",
+            ]
+        );
+
+        // a failing rule is reported on its own, without failing the day
+        let config = crate::config::Config::parse(
+            r##"
+[days.2023.day03]
+rules = [
+    { symbol = "#", combine = "product" },
+    { combine = "mean" },
+    { neighbours = 2, max_neighbours = 3, combine = "sum" },
+    { combine = "sum" },
+]
+"##,
+        )
+        .unwrap();
+        let ctx = Context {
+            params: config.params(2023, 3),
+            ..Context::default()
+        };
+        let answers = solve("9999999999#9999999999\n", &ctx).unwrap();
+        assert_eq!(answers.part(2), Some("0"));
+        let extras: Vec<String> = answers.extras.iter().map(Extra::to_string).collect();
+        assert_eq!(extras[0], "rule `#`, multiplied: error: total overflowed");
+        assert!(extras[1].starts_with("rule 2: error: invalid rule: "));
+        assert_eq!(
+            extras[2],
+            "rule any symbol with 2 neighbours, summed: error: gives both an exact and a ranged neighbour count"
+        );
+        assert_eq!(extras[3], "rule any symbol, summed: 19999999998");
+
+        let rule = |toml: &str| -> Result<Rule> {
+            let rule: Rule = toml::from_str(toml)?;
+            rule.validate().map(|_| rule)
+        };
+        assert!(rule("neighbours = 2\nmin_neighbours = 1\ncombine = \"sum\"").is_err());
+        assert!(rule("symbol = \"##\"\ncombine = \"sum\"").is_err());
+        assert!(rule("combine = \"mean\"").is_err());
+        let sum_all = rule("combine = \"sum\"").unwrap();
+        // no number in the example touches two symbols, so this is part 1
+        assert_eq!(
+            sum_all.total(&Schematic::new(EXAMPLE).unwrap()).unwrap(),
+            4361
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let schematic = Schematic::new("12.\n.*3\n45.\n").unwrap();
+        let star = schematic.cells[schematic.width + 1].unwrap();
+        // 12 touches the star from two cells but is listed once
+        assert_eq!(schematic.part_numbers_around(star), [12, 3, 45]);
+        assert_eq!(schematic.matching(&GEAR).count(), 0);
+        // ragged rows are padded out
+        let schematic = Schematic::new("1\n.*\n").unwrap();
+        assert_eq!(part_1(&schematic).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_lex() {
+        assert_eq!(
+            lex(0, "12é.*").unwrap(),
+            [
+                Token::Number {
+                    value: 12,
+                    start: 0,
+                    end: 1
+                },
+                Token::Symbol {
+                    symbol: 'é',
+                    col: 2
+                },
+                Token::Empty { col: 3 },
+                Token::Symbol {
+                    symbol: '*',
+                    col: 4
+                },
+            ]
+        );
+        // letters and other scripts are symbols too
+        let schematic = Schematic::new("7x.\n..→\n.5.\n").unwrap();
+        assert_eq!(part_1(&schematic).unwrap(), 12);
+
+        let error = |input: &str| format!("{:#}", Schematic::new(input).unwrap_err());
+        assert_eq!(
+            error("...\n.é\t1\n"),
+            "unexpected character `\\t` at row 2, column 3"
+        );
+        assert_eq!(
+            error("..٣\n"),
+            "unexpected character `٣` at row 1, column 3"
+        );
+        assert!(error("1.99999999999999999999").starts_with("part number at row 1, column 3:"));
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 3);
+        b.iter(|| part_1(&Schematic::new(&input).unwrap()));
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 3);
+        b.iter(|| part_2(&Schematic::new(&input).unwrap()).unwrap());
+    }
+}
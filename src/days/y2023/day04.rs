@@ -0,0 +1,192 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::days::{Answers, Context};
+use anyhow::{anyhow, Context as _, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::index, Rng};
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let cards = Card::from_card_list(input)?;
+    for card in cards.iter() {
+        ctx.trace.note(1, || {
+            let matches = card.winning_numbers().len();
+            format!(
+                "card {}: {} matches, {} points",
+                card.id,
+                matches,
+                card.score()
+            )
+        });
+    }
+    Ok(Answers::default()
+        .with_part_1(part_1(&cards))
+        .with_part_2(part_2(&cards)))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    Card::from_card_list(input).map(drop)
+}
+
+fn part_1(cards: &[Card]) -> usize {
+    cards.iter().map(Card::score).sum()
+}
+
+fn part_2(cards: &[Card]) -> usize {
+    let mut result = vec![1; cards.len()];
+    cards.iter().enumerate().for_each(|(idx, card)| {
+        let winner_count = card.winning_numbers().len();
+        for i in (idx + 1)..=(idx + winner_count) {
+            result[i] += result[idx]
+        }
+    });
+
+    result.iter().sum()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Card {
+    id: usize,
+    winning_numbers: Vec<usize>,
+    my_numbers: Vec<usize>,
+}
+
+impl Card {
+    fn winning_numbers(&self) -> Vec<usize> {
+        self.winning_numbers
+            .iter()
+            .filter(|num| self.my_numbers.contains(num))
+            .unique()
+            .cloned()
+            .collect()
+    }
+
+    fn from_card_list(input: &str) -> Result<Vec<Self>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(n, line)| line.parse().with_context(|| format!("line {}", n + 1)))
+            .collect()
+    }
+
+    fn score(&self) -> usize {
+        if self.winning_numbers().is_empty() {
+            return 0;
+        }
+        self.winning_numbers()
+            .iter()
+            .skip(1)
+            .fold(1, |score, _| score * 2)
+    }
+}
+
+impl FromStr for Card {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let malformed = || anyhow!("expected `Card <id>: <winning numbers> | <numbers>`");
+        let (id, card_data) = s.split_once(": ").ok_or_else(malformed)?;
+        let id = id
+            .split_ascii_whitespace()
+            .nth(1)
+            .ok_or_else(malformed)?
+            .parse()?;
+        let (winners, mine) = card_data.split_once(" | ").ok_or_else(malformed)?;
+        let winners = winners.trim();
+        let mine = mine.trim();
+        let winning_numbers = winners
+            .split_ascii_whitespace()
+            .map(|num| num.parse())
+            .collect::<Result<_, _>>()?;
+        let my_numbers = mine
+            .split_ascii_whitespace()
+            .map(|num| num.parse())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            id,
+            winning_numbers,
+            my_numbers,
+        })
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let numbers = |nums: &[usize]| nums.iter().map(|num| format!("{:>2}", num)).join(" ");
+        write!(
+            f,
+            "Card {:>3}: {} | {}",
+            self.id,
+            numbers(&self.winning_numbers),
+            numbers(&self.my_numbers)
+        )
+    }
+}
+
+/// Generates `size` cards of ten winning numbers and twenty-five of our own.
+/// No card wins more copies than there are cards after it.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for id in 1..=size {
+        let matches = rng.gen_range(0..=(size - id).min(10));
+        // the first ten are winners, the rest are numbers only we hold
+        let numbers: Vec<usize> = index::sample(rng, 99, 35)
+            .into_iter()
+            .map(|n| n + 1)
+            .collect();
+        let card = Card {
+            id,
+            winning_numbers: numbers[..10].to_vec(),
+            my_numbers: numbers[..matches]
+                .iter()
+                .chain(&numbers[10..35 - matches])
+                .cloned()
+                .collect(),
+        };
+        output.push_str(&format!("{}\n", card));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    extern crate test;
+
+    use proptest::prelude::*;
+    use test::Bencher;
+
+    fn arb_card() -> impl Strategy<Value = Card> {
+        let numbers = |len| prop::collection::vec(1..100usize, len);
+        (1..1000usize, numbers(1..11), numbers(1..26)).prop_map(
+            |(id, winning_numbers, my_numbers)| Card {
+                id,
+                winning_numbers,
+                my_numbers,
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_card_round_trip(card in arb_card()) {
+            let rendered = card.to_string();
+            let parsed: Card = rendered.parse().unwrap();
+            prop_assert_eq!(parsed.to_string(), rendered);
+            prop_assert_eq!(parsed, card);
+        }
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 4);
+        b.iter(|| part_1(&Card::from_card_list(&input).unwrap()));
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 4);
+        b.iter(|| part_2(&Card::from_card_list(&input).unwrap()));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::days::{Answers, Context};
+use anyhow::{anyhow, bail, Context as _, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::index, Rng};
+use rangemap::RangeMap;
+use std::ops::Range;
+
+type Seeds = Vec<Range<i64>>;
+type SeedMaps = Vec<RangeMap<i64, i64>>;
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Answers> {
+    let (seeds, seed_maps) = process(input)?;
+    Ok(Answers::default().with_part_1(part_1(seeds, seed_maps)))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    process(input).map(drop)
+}
+
+fn part_1(mut seeds: Seeds, seed_maps: SeedMaps) -> i64 {
+    for map in seed_maps {
+        seeds = apply_map(&mut seeds, &map);
+    }
+    seeds.iter().map(|range| range.start).min().unwrap()
+}
+
+fn process(input: &str) -> Result<(Seeds, SeedMaps)> {
+    let mut blocks = input.split("\n\n");
+
+    let (_, seeds) = blocks
+        .next()
+        .and_then(|seeds| seeds.split_once(": "))
+        .ok_or_else(|| anyhow!("expected `seeds: <ranges>`"))?;
+    let seeds: Vec<Range<i64>> = seeds
+        .split_ascii_whitespace()
+        .chunks(2)
+        .into_iter()
+        .map(|mut chunk| {
+            let start: i64 = chunk.next().unwrap().parse()?;
+            let length: i64 = chunk
+                .next()
+                .ok_or_else(|| anyhow!("seed range {} has no length", start))?
+                .parse()?;
+            range(start, length)
+        })
+        .collect::<Result<_>>()
+        .context("seeds")?;
+    let mut seed_maps = Vec::new();
+    for block in blocks {
+        let mut seed_map: RangeMap<i64, i64> = RangeMap::new();
+        for line in block.lines().skip(1) {
+            let (src, offset) = map_entry(line).with_context(|| format!("map entry `{}`", line))?;
+            seed_map.insert(src, offset);
+        }
+        seed_maps.push(seed_map);
+    }
+
+    Ok((seeds, seed_maps))
+}
+
+/// Reads a `<destination> <source> <length>` line as the source range and
+/// the offset it maps by.
+fn map_entry(line: &str) -> Result<(Range<i64>, i64)> {
+    let values: Vec<i64> = line
+        .split_ascii_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+    let [dst, src, length] = values[..] else {
+        bail!("expected three values");
+    };
+    let offset = dst
+        .checked_sub(src)
+        .ok_or_else(|| anyhow!("offset out of bounds"))?;
+    // mapped values land in the destination range, so it has to fit too
+    range(dst, length)?;
+    Ok((range(src, length)?, offset))
+}
+
+/// The range of `length` values from `start`, which must be non-empty.
+fn range(start: i64, length: i64) -> Result<Range<i64>> {
+    if length <= 0 {
+        bail!("range length must be positive, got {}", length);
+    }
+    let end = start
+        .checked_add(length)
+        .ok_or_else(|| anyhow!("range {}+{} out of bounds", start, length))?;
+    Ok(start..end)
+}
+
+fn apply_map(inputs: &mut Vec<Range<i64>>, map: &RangeMap<i64, i64>) -> Vec<Range<i64>> {
+    let mut out = Vec::new();
+    while let Some(input) = inputs.pop() {
+        // map the first overlap only; the leftovers go back on the stack and
+        // find any further overlaps themselves
+        if let Some((range, offset)) = map.overlapping(&input).next() {
+            let start = std::cmp::max(input.start, range.start);
+            let end = std::cmp::min(input.end, range.end);
+            out.push(start + offset..end + offset);
+            if input.start < start {
+                inputs.push(input.start..start);
+            }
+            if end < input.end {
+                inputs.push(end..input.end);
+            }
+        } else {
+            out.push(input);
+        }
+    }
+
+    out
+}
+
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+const VALUE_LIMIT: i64 = 1 << 32;
+
+/// Generates an almanac whose seven maps each hold `size` ranges, with one
+/// seed range for every four map ranges.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let seeds = (0..(size / 4).max(1))
+        .map(|_| {
+            let start = rng.gen_range(0..VALUE_LIMIT / 2);
+            let length = rng.gen_range(1..=VALUE_LIMIT / 2 / size as i64);
+            format!("{} {}", start, length)
+        })
+        .join(" ");
+
+    let mut output = format!("seeds: {}\n", seeds);
+    for name in MAP_NAMES {
+        output.push_str(&format!("\n{} map:\n", name));
+        // pairs of distinct sorted points bound non-overlapping source ranges
+        let mut bounds: Vec<i64> = index::sample(rng, VALUE_LIMIT as usize, size * 2)
+            .into_iter()
+            .map(|n| n as i64)
+            .collect();
+        bounds.sort_unstable();
+        for (src, end) in bounds.into_iter().tuples() {
+            let length = end - src;
+            let dst = rng.gen_range(0..=VALUE_LIMIT - length);
+            output.push_str(&format!("{} {} {}\n", dst, src, length));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    extern crate test;
+
+    use proptest::prelude::*;
+    use test::Bencher;
+
+    fn arb_ranges() -> impl Strategy<Value = Seeds> {
+        prop::collection::vec((0..1000i64, 1..100i64), 0..10).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| start..start + len)
+                .collect()
+        })
+    }
+
+    fn arb_seed_map() -> impl Strategy<Value = RangeMap<i64, i64>> {
+        prop::collection::vec((0..1000i64, 1..100i64, -500..500i64), 0..10).prop_map(|entries| {
+            let mut map = RangeMap::new();
+            for (src, len, offset) in entries {
+                map.insert(src..src + len, offset);
+            }
+            map
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_apply_map_preserves_length(mut ranges in arb_ranges(), map in arb_seed_map()) {
+            let total = |ranges: &[Range<i64>]| ranges.iter().map(|r| r.end - r.start).sum::<i64>();
+            let before = total(&ranges);
+            let mapped = apply_map(&mut ranges, &map);
+            prop_assert_eq!(total(&mapped), before);
+        }
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 5);
+        let (seeds, seed_map) = process(&input).unwrap();
+        b.iter(|| part_1(seeds.clone(), seed_map.clone()));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    days::{Answers, Context},
+    util::math,
+};
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::index, Rng};
+
+pub fn solve(input: &str, _ctx: &Context) -> Result<Answers> {
+    let races = Race::from_race_list(input)?;
+    let p2_race = Race::from_race_list_p2(input)?;
+
+    Ok(Answers::default()
+        .with_part_1(part_1(&races)?)
+        .with_part_2(p2_race.ways_to_win()?))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    Race::from_race_list(input)?;
+    Race::from_race_list_p2(input)?;
+    Ok(())
+}
+
+fn part_1(races: &[Race]) -> Result<usize> {
+    races.iter().try_fold(1usize, |product, race| {
+        product
+            .checked_mul(race.ways_to_win()?)
+            .ok_or_else(|| anyhow!("product of the ways to win overflowed"))
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Race {
+    time: i64,
+    distance_record: i64,
+}
+
+impl Race {
+    fn from_race_list(race_list: &str) -> Result<Vec<Race>> {
+        let (times, distances) = Race::split_lines(race_list)?;
+
+        let times: Vec<i64> = times
+            .split_ascii_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        let distances: Vec<i64> = distances
+            .split_ascii_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        if times.len() != distances.len() {
+            bail!(
+                "{} times but {} distance records",
+                times.len(),
+                distances.len()
+            );
+        }
+
+        Ok(times
+            .into_iter()
+            .zip(distances)
+            .map(|(time, distance)| Race {
+                time,
+                distance_record: distance,
+            })
+            .collect())
+    }
+
+    fn from_race_list_p2(race_list: &str) -> Result<Race> {
+        let (times, distances) = Race::split_lines(race_list)?;
+        let time: String = times.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+        let distance: String = distances
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+
+        Ok(Race {
+            time: time.parse()?,
+            distance_record: distance.parse()?,
+        })
+    }
+
+    /// The values following the `Time:` and `Distance:` labels.
+    fn split_lines(race_list: &str) -> Result<(&str, &str)> {
+        let mut lines = race_list.lines();
+        let mut values = |label| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(label))
+                .ok_or_else(|| anyhow!("expected a line starting with `{}`", label))
+        };
+        Ok((values("Time:")?, values("Distance:")?))
+    }
+
+    fn ways_to_win(&self) -> Result<usize> {
+        // charging for t goes t·(time - t), so the winning charge times are
+        // where t² - time·t + record ≤ 0, between the parabola's roots
+        let Some(wins) = math::quadratic_bounds(1, -self.time, self.distance_record)? else {
+            return Ok(0);
+        };
+        let (first, last) = ((*wins.start()).max(0), (*wins.end()).min(self.time));
+        Ok((last - first + 1).max(0) as usize)
+    }
+}
+
+/// Generates a race sheet whose part 2 race lasts around `size` milliseconds,
+/// split into up to four part 1 races.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let time = rng.gen_range(size as u64 / 2..=size.max(1) as u64);
+    let distance = rng.gen_range(0..=time * time / 4);
+    let (time, distance) = (time.to_string(), distance.to_string());
+
+    // races can only be split where the next digit is not a leading zero
+    let cuts = |digits: &str| {
+        (1..digits.len())
+            .filter(|&i| digits.as_bytes()[i] != b'0')
+            .collect::<Vec<_>>()
+    };
+    let (time_cuts, distance_cuts) = (cuts(&time), cuts(&distance));
+    let races = rng.gen_range(1..=4usize.min(time_cuts.len() + 1).min(distance_cuts.len() + 1));
+    let mut split = |digits: &str, cuts: &[usize]| {
+        let mut chosen: Vec<usize> = index::sample(rng, cuts.len(), races - 1)
+            .into_iter()
+            .map(|i| cuts[i])
+            .collect();
+        chosen.sort_unstable();
+        std::iter::once(0)
+            .chain(chosen)
+            .chain(std::iter::once(digits.len()))
+            .tuple_windows()
+            .map(|(start, end)| digits[start..end].to_owned())
+            .collect::<Vec<_>>()
+    };
+    let times = split(&time, &time_cuts);
+    let distances = split(&distance, &distance_cuts);
+
+    let widths: Vec<usize> = times
+        .iter()
+        .zip(distances.iter())
+        .map(|(t, d)| t.len().max(d.len()))
+        .collect();
+    let row = |values: &[String]| {
+        values
+            .iter()
+            .zip(widths.iter())
+            .map(|(value, width)| format!("{:>width$}", value, width = width))
+            .join("  ")
+    };
+    format!(
+        "Time:      {}\nDistance:  {}\n",
+        row(&times),
+        row(&distances)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{differential, util};
+    extern crate test;
+
+    use test::Bencher;
+
+    #[test]
+    fn test_ways_to_win() {
+        let race = Race {
+            time: 7,
+            distance_record: 9,
+        };
+        assert_eq!(race.ways_to_win().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_product_overflow() {
+        let input = "Time: 1000000000000 1000000000000\nDistance: 0 0\n";
+        assert!(part_1(&Race::from_race_list(input).unwrap()).is_err());
+    }
+
+    fn ways_to_win_reference(race: &Race) -> usize {
+        (0..=race.time)
+            .filter(|charge_time| charge_time * (race.time - charge_time) >= race.distance_record)
+            .count()
+    }
+
+    #[test]
+    fn test_ways_to_win_matches_reference() {
+        differential::check(
+            2023,
+            6,
+            &[1, 10, 1000, 100000],
+            |input| {
+                let races = Race::from_race_list(input).unwrap();
+                let race = Race::from_race_list_p2(input).unwrap();
+                (
+                    races.iter().map(ways_to_win_reference).product::<usize>(),
+                    ways_to_win_reference(&race),
+                )
+            },
+            |input| {
+                let race = Race::from_race_list_p2(input).unwrap();
+                (
+                    part_1(&Race::from_race_list(input).unwrap()).unwrap(),
+                    race.ways_to_win().unwrap(),
+                )
+            },
+        );
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 6);
+        b.iter(|| part_1(&Race::from_race_list(&input).unwrap()));
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 6);
+        b.iter(|| Race::from_race_list_p2(&input).unwrap().ways_to_win());
+    }
+}
@@ -1,14 +1,17 @@
 use std::{cmp::Ordering, collections::HashMap, error::Error, fmt::Display, str::FromStr};
 
-use crate::util;
-use anyhow::Result;
+use crate::days::{Answers, Context};
+use anyhow::{anyhow, bail, Context as _, Result};
+use rand::{rngs::StdRng, Rng};
 
-pub fn run() -> Result<()> {
-    let raw_input = util::read_input("inputs/day07.txt")?;
-    let hands = Hand::from_hand_list(&raw_input);
+pub fn solve(input: &str, _ctx: &Context) -> Result<Answers> {
+    let hands = Hand::from_hand_list(input)?;
 
-    println!("part 2: {}", part_1(hands.clone()));
-    Ok(())
+    Ok(Answers::default().with_part_2(part_1(hands)))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    Hand::from_hand_list(input).map(drop)
 }
 
 fn part_1(mut hands: Vec<Hand>) -> usize {
@@ -25,8 +28,12 @@ fn part_1(mut hands: Vec<Hand>) -> usize {
 struct Hand(Card, Card, Card, Card, Card, i64);
 
 impl Hand {
-    fn from_hand_list(hands: &str) -> Vec<Hand> {
-        hands.lines().flat_map(Hand::from_str).collect()
+    fn from_hand_list(hands: &str) -> Result<Vec<Hand>> {
+        hands
+            .lines()
+            .enumerate()
+            .map(|(n, line)| line.parse().with_context(|| format!("line {}", n + 1)))
+            .collect()
     }
 
     fn bid(&self) -> i64 {
@@ -35,16 +42,24 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = CardFromStringErr;
+    type Err = anyhow::Error;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand, bid) = s.split_once(' ').unwrap();
-        let bid = bid.parse().unwrap();
-        let c1 = hand[0..1].parse()?;
-        let c2 = hand[1..2].parse()?;
-        let c3 = hand[2..3].parse()?;
-        let c4 = hand[3..4].parse()?;
-        let c5 = hand[4..].parse()?;
+    fn from_str(s: &str) -> Result<Self> {
+        let (hand, bid) = s
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("expected `<cards> <bid>`"))?;
+        let bid = bid.parse()?;
+        let cards = hand
+            .chars()
+            .map(|card| {
+                card.to_string()
+                    .parse()
+                    .with_context(|| format!("card `{}`", card))
+            })
+            .collect::<Result<Vec<Card>>>()?;
+        let [c1, c2, c3, c4, c5] = cards[..] else {
+            bail!("expected five cards, got {}", cards.len());
+        };
 
         Ok(Hand(c1, c2, c3, c4, c5, bid))
     }
@@ -104,6 +119,14 @@ enum HandRank {
 
 impl From<&Hand> for HandRank {
     fn from(value: &Hand) -> Self {
+        HandRank::new(value, true)
+    }
+}
+
+impl HandRank {
+    /// Ranks a hand, optionally letting jokers stand in for whichever card
+    /// makes the strongest hand.
+    fn new(value: &Hand, jokers: bool) -> Self {
         let mut card_counts: HashMap<Card, usize> = HashMap::new();
         let c1 = card_counts.entry(value.0).or_insert(0);
         *c1 += 1;
@@ -116,7 +139,7 @@ impl From<&Hand> for HandRank {
         let c5 = card_counts.entry(value.4).or_insert(0);
         *c5 += 1;
 
-        if let Some(joker_count) = card_counts.remove(&Card::J) {
+        if let Some(joker_count) = jokers.then(|| card_counts.remove(&Card::J)).flatten() {
             let most = card_counts
                 .iter()
                 .max_by(|(_, count), (_, count_b)| count.cmp(count_b))
@@ -129,7 +152,7 @@ impl From<&Hand> for HandRank {
         match card_counts.len() {
             1 | 0 => HandRank::FiveOAK,
             2 => {
-                for (_card, count) in card_counts.iter() {
+                for count in card_counts.values() {
                     if *count == 4 {
                         return HandRank::FourOAK;
                     }
@@ -137,7 +160,7 @@ impl From<&Hand> for HandRank {
                 HandRank::FullHouse
             }
             3 => {
-                for (_card, count) in card_counts.iter() {
+                for count in card_counts.values() {
                     if *count == 3 {
                         return HandRank::ThreeOAK;
                     }
@@ -191,6 +214,37 @@ impl FromStr for Card {
     }
 }
 
+impl Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Card::A => "A",
+            Card::K => "K",
+            Card::Q => "Q",
+            Card::J => "J",
+            Card::T => "T",
+            Card::N => "9",
+            Card::E => "8",
+            Card::Sv => "7",
+            Card::S => "6",
+            Card::Fi => "5",
+            Card::F => "4",
+            Card::Thr => "3",
+            Card::Tw => "2",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}{} {}",
+            self.0, self.1, self.2, self.3, self.4, self.5
+        )
+    }
+}
+
 #[derive(Debug)]
 struct CardFromStringErr {}
 
@@ -202,13 +256,74 @@ impl Display for CardFromStringErr {
 
 impl Error for CardFromStringErr {}
 
+const CARD_LABELS: &[u8] = b"23456789TJQKA";
+
+/// Generates `size` hands with bids of up to 1000.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        for _ in 0..5 {
+            output.push(char::from(CARD_LABELS[rng.gen_range(0..CARD_LABELS.len())]));
+        }
+        output.push_str(&format!(" {}\n", rng.gen_range(1..=1000)));
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util;
     extern crate test;
 
+    use proptest::prelude::*;
     use test::Bencher;
 
+    fn arb_card() -> impl Strategy<Value = Card> {
+        prop::sample::select(vec![
+            Card::J,
+            Card::Tw,
+            Card::Thr,
+            Card::F,
+            Card::Fi,
+            Card::S,
+            Card::Sv,
+            Card::E,
+            Card::N,
+            Card::T,
+            Card::Q,
+            Card::K,
+            Card::A,
+        ])
+    }
+
+    fn arb_hand() -> impl Strategy<Value = Hand> {
+        (
+            arb_card(),
+            arb_card(),
+            arb_card(),
+            arb_card(),
+            arb_card(),
+            0..10000i64,
+        )
+            .prop_map(|(c1, c2, c3, c4, c5, bid)| Hand(c1, c2, c3, c4, c5, bid))
+    }
+
+    proptest! {
+        #[test]
+        fn test_hand_round_trip(hand in arb_hand()) {
+            let rendered = hand.to_string();
+            let parsed: Hand = rendered.parse().unwrap();
+            prop_assert_eq!(parsed.to_string(), rendered);
+            prop_assert_eq!((parsed, parsed.bid()), (hand, hand.bid()));
+        }
+
+        #[test]
+        fn test_jokers_never_weaken_hand(hand in arb_hand()) {
+            prop_assert!(HandRank::new(&hand, true) >= HandRank::new(&hand, false));
+        }
+    }
+
     #[test]
     fn test_hand_to_rank() {
         let test_hand = &Hand(Card::A, Card::A, Card::A, Card::E, Card::E, 0);
@@ -223,8 +338,8 @@ mod tests {
 
     #[bench]
     fn bench_p2(b: &mut Bencher) {
-        let input = util::read_input("inputs/day07.txt").unwrap();
-        let hands = Hand::from_hand_list(&input);
+        let input = util::bench_input(2023, 7);
+        let hands = Hand::from_hand_list(&input).unwrap();
         b.iter(|| part_1(hands.clone()));
     }
 }
@@ -0,0 +1,328 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter,
+};
+
+use crate::{
+    days::{Answers, Context},
+    util::{graph, math},
+};
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+const START_NODE: &str = "AAA";
+const END_NODE: &str = "ZZZ";
+
+/// Most steps part 2 keeps track of while lining the ghosts up.
+const MAX_CANDIDATES: usize = 1_000_000;
+
+type NodeMap = HashMap<String, (String, String)>;
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let start: String = ctx.params.get_or("start", START_NODE.to_owned())?;
+    let end: String = ctx.params.get_or("end", END_NODE.to_owned())?;
+    let (instructions, node_map) = process(input)?;
+    if let Some(node) = [&start, &end]
+        .into_iter()
+        .find(|n| !node_map.contains_key(*n))
+    {
+        bail!("no node named `{}`", node);
+    }
+    let part_1 = part_1(&instructions, &node_map, &start, &end)
+        .ok_or_else(|| anyhow!("`{}` is never reached from `{}`", end, start))?;
+    let part_2 = part_2(&instructions, &node_map)?
+        .ok_or_else(|| anyhow!("the ghosts are never all on end nodes at once"))?;
+    Ok(Answers::default().with_part_1(part_1).with_part_2(part_2))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    process(input).map(drop)
+}
+
+/// Where a walker is: at which node, and how far through the instructions.
+type Position<'a> = (&'a str, usize);
+
+/// Where the walk from `start` goes, beginning with `start` itself.
+fn walk<'a>(
+    instructions: &'a [char],
+    node_map: &'a NodeMap,
+    start: &'a str,
+) -> impl Iterator<Item = Position<'a>> + 'a {
+    iter::successors(Some((start, 0)), |position| {
+        Some(step(instructions, node_map, position))
+    })
+}
+
+fn step<'a>(instructions: &[char], node_map: &'a NodeMap, &(node, i): &Position) -> Position<'a> {
+    let (left, right) = &node_map[node];
+    let next = match instructions[i] {
+        'L' => left,
+        _ => right,
+    };
+    (next.as_str(), (i + 1) % instructions.len())
+}
+
+/// How long the walk from `start` loops round before it repeats itself: how
+/// many steps until it enters the loop, and how many steps it takes.
+fn find_loop(instructions: &[char], node_map: &NodeMap, start: &str) -> (usize, usize) {
+    graph::brent((start, 0), |position| {
+        step(instructions, node_map, position)
+    })
+}
+
+fn part_1(instructions: &[char], node_map: &NodeMap, start: &str, end: &str) -> Option<usize> {
+    // anything not reached by the end of the first lap never will be
+    let (offset, length) = find_loop(instructions, node_map, start);
+    walk(instructions, node_map, start)
+        .take(offset + length)
+        .position(|(node, _)| node == end)
+}
+
+/// Every ghost ends up going round a loop, passing the same end nodes each
+/// lap. Once all of them are in their loops, they line up at the steps that
+/// agree with one end node of each loop, modulo the loop's length.
+fn part_2(instructions: &[char], node_map: &NodeMap) -> Result<Option<usize>> {
+    let starts: Vec<&String> = node_map.keys().filter(|k| k.ends_with('A')).collect();
+    let loops: Vec<(usize, usize)> = starts
+        .iter()
+        .map(|start| find_loop(instructions, node_map, start))
+        .collect();
+    let settled = loops.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+
+    // before then they can only meet by walking together
+    let mut walks: Vec<_> = starts
+        .iter()
+        .map(|start| walk(instructions, node_map, start))
+        .collect();
+    for step in 0..settled {
+        let mut on_ends = walks
+            .iter_mut()
+            .map(|walk| walk.next().unwrap().0.ends_with('Z'));
+        if on_ends.all(|on_end| on_end) {
+            return Ok(Some(step));
+        }
+    }
+
+    let ends: Vec<Vec<(i64, i64)>> = starts
+        .iter()
+        .zip(loops)
+        .map(|(start, (offset, length))| {
+            walk(instructions, node_map, start)
+                .enumerate()
+                .take(offset + length)
+                .skip(offset)
+                .filter(|(_, (node, _))| node.ends_with('Z'))
+                .map(|(step, _)| (step as i64, length as i64))
+                .collect()
+        })
+        .collect();
+
+    // Lining the ghosts up one at a time keeps only the steps that still
+    // agree, at most one per residue of the combined period, rather than
+    // every combination of end nodes.
+    let (mut candidates, mut period) = (BTreeSet::from([0]), 1);
+    for hits in ends {
+        let (mut merged, mut merged_period) = (BTreeSet::new(), period);
+        for &candidate in candidates.iter() {
+            for &hit in hits.iter() {
+                if let Some((step, lcm)) = math::crt(&[(candidate, period), hit]) {
+                    merged.insert(step);
+                    merged_period = lcm;
+                    if merged.len() > MAX_CANDIDATES {
+                        bail!(
+                            "the ghosts could line up at more than {} steps",
+                            MAX_CANDIDATES
+                        );
+                    }
+                }
+            }
+        }
+        (candidates, period) = (merged, merged_period);
+    }
+    Ok(candidates
+        .into_iter()
+        .map(|step| {
+            let behind = (settled as i64 - step).max(0);
+            (step + (behind + period - 1) / period * period) as usize
+        })
+        .min())
+}
+
+fn process(input: &str) -> Result<(Vec<char>, NodeMap)> {
+    let (instructions, node_map) = input
+        .split_once("\n\n")
+        .ok_or_else(|| anyhow!("expected instructions and nodes separated by a blank line"))?;
+    if let Some(c) = instructions.chars().find(|c| !matches!(c, 'L' | 'R')) {
+        bail!("invalid instruction `{}`", c);
+    }
+    if instructions.is_empty() {
+        bail!("no instructions");
+    }
+    let instructions = instructions.chars().collect();
+
+    let node_map: NodeMap = node_map
+        .lines()
+        .map(|line| {
+            let malformed = || anyhow!("expected `<node> = (<left>, <right>)`, got `{}`", line);
+            let (node, next_nodes) = line.split_once(" = ").ok_or_else(malformed)?;
+            let node = String::from(node);
+            let next_nodes = next_nodes.replace('(', "");
+            let next_nodes = next_nodes.replace(')', "");
+            let (left, right) = next_nodes.split_once(", ").ok_or_else(malformed)?;
+            Ok((node, (String::from(left), String::from(right))))
+        })
+        .collect::<Result<_>>()?;
+
+    for (node, (left, right)) in node_map.iter() {
+        if let Some(next) = [left, right]
+            .into_iter()
+            .find(|n| !node_map.contains_key(*n))
+        {
+            bail!("node `{}` leads to unknown node `{}`", node, next);
+        }
+    }
+
+    Ok((instructions, node_map))
+}
+
+/// Generates a network of roughly `size` nodes walked by up to six ghosts,
+/// one of which runs from `AAA` to `ZZZ`.
+///
+/// Each ghost follows a cycle whose length is a multiple of the instruction
+/// count, so every node is always left by the same instruction and the
+/// unused branch can point anywhere.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let ghosts = rng.gen_range(1..=(size / 8).clamp(1, 6));
+    let instruction_count = (size / ghosts / 4).max(1);
+    let instructions: Vec<char> = (0..instruction_count)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let cycle_lengths: Vec<usize> = (0..ghosts)
+        .map(|_| instruction_count * rng.gen_range(1..=7))
+        .collect();
+    let node_count: usize = cycle_lengths.iter().map(|len| len + 1).sum();
+    // interior names use letters other than A and Z, which mark the ends
+    let name_len = (3..)
+        .find(|&len| 24usize.pow(len as u32) >= node_count)
+        .unwrap();
+    let name = |mut n: usize, last: Option<char>| {
+        let mut name: Vec<char> = (0..name_len)
+            .map(|_| {
+                let c = char::from(b'B' + (n % 24) as u8);
+                n /= 24;
+                c
+            })
+            .collect();
+        if let Some(last) = last {
+            name[name_len - 1] = last;
+        }
+        name.into_iter().collect::<String>()
+    };
+
+    let mut paths = Vec::new();
+    let mut next_name = 0;
+    for (ghost, cycle_length) in cycle_lengths.iter().enumerate() {
+        let mut path = Vec::new();
+        for step in 0..=*cycle_length {
+            let last = match step {
+                0 => Some('A'),
+                s if s == *cycle_length => Some('Z'),
+                _ => None,
+            };
+            path.push(name(next_name, last));
+            next_name += 1;
+        }
+        if ghost == 0 {
+            path[0] = String::from(START_NODE);
+            path[*cycle_length] = String::from(END_NODE);
+        }
+        paths.push(path);
+    }
+    let all_nodes: Vec<String> = paths.iter().flatten().cloned().collect();
+
+    let mut lines = Vec::new();
+    for path in paths.iter() {
+        for (step, node) in path.iter().enumerate() {
+            // the end node loops back to where the start node leads
+            let next = if step + 1 < path.len() {
+                &path[step + 1]
+            } else {
+                &path[1]
+            };
+            let other = all_nodes.choose(rng).unwrap();
+            let (left, right) = match instructions[step % instruction_count] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            lines.push(format!("{} = ({}, {})", node, left, right));
+        }
+    }
+    lines.shuffle(rng);
+
+    format!(
+        "{}\n\n{}\n",
+        instructions.iter().collect::<String>(),
+        lines.iter().join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    extern crate test;
+
+    use test::Bencher;
+
+    #[test]
+    fn test_part_2() {
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+        let (instructions, node_map) = process(input).unwrap();
+        assert_eq!(part_2(&instructions, &node_map).unwrap(), Some(6));
+    }
+
+    #[test]
+    fn test_part_2_many_ends() {
+        // eight ghosts circling rings made only of end nodes, 12 of them
+        // each, would be 12⁸ combinations of ends to try
+        let mut input = String::from("L\n\n");
+        for ghost in 0..8 {
+            input += &format!("G{}A = (G{}N0Z, G{}N0Z)\n", ghost, ghost, ghost);
+            for node in 0..12 {
+                let next = (node + 1) % 12;
+                input += &format!(
+                    "G{}N{}Z = (G{}N{}Z, G{}N{}Z)\n",
+                    ghost, node, ghost, next, ghost, next
+                );
+            }
+        }
+        let (instructions, node_map) = process(&input).unwrap();
+        assert_eq!(part_2(&instructions, &node_map).unwrap(), Some(1));
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let input = util::bench_input(2023, 8);
+        let (instructions, node_map) = process(&input).unwrap();
+        b.iter(|| part_1(&instructions, &node_map, START_NODE, END_NODE))
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let input = util::bench_input(2023, 8);
+        let (instructions, node_map) = process(&input).unwrap();
+        b.iter(|| part_2(&instructions, &node_map))
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    days::{Answers, Context},
+    util::math,
+};
+use anyhow::{anyhow, Context as _, Result};
+use itertools::Itertools;
+use rand::{rngs::StdRng, Rng};
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let input = process(input)?;
+    for (n, history) in input.iter().enumerate() {
+        ctx.trace.note(1, || match math::extrapolate(history) {
+            Ok(next) => format!("history {}: next {}", n + 1, next),
+            Err(e) => format!("history {}: {:#}", n + 1, e),
+        });
+        ctx.trace.note(2, || {
+            let reversed: Vec<i64> = history.iter().rev().copied().collect();
+            match math::extrapolate(&reversed) {
+                Ok(previous) => format!("history {}: previous {}", n + 1, previous),
+                Err(e) => format!("history {}: {:#}", n + 1, e),
+            }
+        });
+    }
+    Ok(Answers::default()
+        .with_part_1(part_1(input.clone())?)
+        .with_part_2(part_2(input)?))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    process(input).map(drop)
+}
+
+fn part_1(input: Vec<Vec<i64>>) -> Result<i64> {
+    sum(input.iter().map(|l| math::extrapolate(l)))
+}
+
+fn part_2(mut input: Vec<Vec<i64>>) -> Result<i64> {
+    sum(input.iter_mut().map(|l| {
+        l.reverse();
+        math::extrapolate(l)
+    }))
+}
+
+fn sum(mut values: impl Iterator<Item = Result<i64>>) -> Result<i64> {
+    values.try_fold(0i64, |total, value| {
+        total
+            .checked_add(value?)
+            .ok_or_else(|| anyhow!("sum of extrapolated values overflowed"))
+    })
+}
+
+fn process(input: &str) -> Result<Vec<Vec<i64>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, line)| {
+            line.split_ascii_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .with_context(|| format!("line {}", n + 1))
+        })
+        .collect()
+}
+
+/// Generates `size` histories of 21 values, each sampled from a random
+/// polynomial of degree three or less.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size {
+        let degree = rng.gen_range(0..=3);
+        let coefficients: Vec<i32> = (0..=degree).map(|_| rng.gen_range(-5..=5)).collect();
+        let history = (0..21)
+            .map(|x: i32| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |value, coefficient| value * x + coefficient)
+            })
+            .join(" ");
+        output.push_str(&history);
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util;
+    extern crate test;
+
+    use test::Bencher;
+
+    #[test]
+    fn test_part_1() {
+        let test_input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+        let input = process(test_input).unwrap();
+        assert_eq!(part_1(input.clone()).unwrap(), 114);
+        assert_eq!(part_2(input).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input = process("9223372036854775807 -9223372036854775808\n").unwrap();
+        assert!(part_1(input.clone()).is_err());
+        assert!(part_2(input).is_err());
+
+        let input = process("9223372036854775807\n1\n").unwrap();
+        assert!(part_1(input).is_err());
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let raw_input = util::bench_input(2023, 9);
+        b.iter(|| {
+            let input = process(&raw_input).unwrap();
+            part_1(input).unwrap();
+        })
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let raw_input = util::bench_input(2023, 9);
+        b.iter(|| {
+            let input = process(&raw_input).unwrap();
+            part_2(input).unwrap();
+        })
+    }
+}
@@ -0,0 +1,431 @@
+use crate::{
+    days::{Answers, Context},
+    util::{
+        graph::{self, Graph},
+        math,
+    },
+    viz::{Frames, Grid, Rgb},
+};
+use anyhow::{bail, Ok, Result};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+/// Roughly how many frames the walk around the loop is drawn in.
+const WALK_FRAMES: usize = 60;
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let world: World = input.parse()?;
+    let boundary = world.explore();
+    if ctx.frames.is_enabled() {
+        world.draw(&boundary, &ctx.frames);
+    }
+    let farthest = graph::bfs(&world, world.start).into_values().max();
+    Ok(Answers::default()
+        .with_part_1(farthest.unwrap_or(0))
+        .with_part_2(interior_area(&boundary)?))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    input.parse::<World>().map(drop)
+}
+
+#[derive(Debug)]
+struct World {
+    data: HashMap<(usize, usize), Node>,
+    start: (usize, usize),
+}
+
+impl World {
+    /// The tiles of the loop through the start, in order around it.
+    fn explore(&self) -> Vec<(usize, usize)> {
+        // every tile on the loop has just the two neighbours either side, so
+        // going deep walks straight round it
+        graph::dfs(self, self.start)
+    }
+
+    /// Tiles inside the loop, found by scanning each row and flipping between
+    /// outside and inside whenever the loop crosses it.
+    fn enclosed(&self, boundary: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        let on_loop: HashSet<&(usize, usize)> = boundary.iter().collect();
+        let (height, width) = self.size();
+        let mut enclosed = Vec::new();
+        for row in 0..height {
+            let mut inside = false;
+            for col in 0..width {
+                if on_loop.contains(&(row, col)) {
+                    // only count the pipes heading up, so a run along the
+                    // row flips once if it crosses and not at all otherwise
+                    if self.data[&(row, col)].has_upper_connection() {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    enclosed.push((row, col));
+                }
+            }
+        }
+        enclosed
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let height = self.data.keys().map(|c| c.0 + 1).max().unwrap_or(0);
+        let width = self.data.keys().map(|c| c.1 + 1).max().unwrap_or(0);
+        (height, width)
+    }
+
+    /// Draws the walk around the loop for part 1, then the tiles it
+    /// encloses for part 2.
+    fn draw(&self, boundary: &[(usize, usize)], frames: &Frames) {
+        let (height, width) = self.size();
+        let mut grid = Grid::new(width, height, Rgb::BLACK);
+        for &(row, col) in self.data.keys() {
+            grid.set(row, col, Rgb::GREY);
+        }
+        let step = (boundary.len() / WALK_FRAMES).max(1);
+        for (n, &(row, col)) in boundary.iter().enumerate() {
+            grid.set(row, col, Rgb::ramp(n as f64 / boundary.len() as f64));
+            if (n + 1) % step == 0 || n + 1 == boundary.len() {
+                frames.record(1, || grid.clone());
+            }
+        }
+        for (row, col) in self.enclosed(boundary) {
+            grid.set(row, col, Rgb::WHITE);
+        }
+        grid.set(self.start.0, self.start.1, Rgb::RED);
+        frames.record(2, || grid);
+    }
+}
+
+impl Graph for World {
+    type Node = (usize, usize);
+
+    /// The tiles joined to this one by pipes running both ways.
+    fn neighbours(&self, &(row, col): &(usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let node = self.data.get(&(row, col));
+        let candidates = node.into_iter().flat_map(move |node| {
+            [
+                (node.has_upper_connection() && row > 0).then(|| (row - 1, col)),
+                (node.has_left_connection() && col > 0).then(|| (row, col - 1)),
+                node.has_lower_connection().then_some((row + 1, col)),
+                node.has_right_connection().then_some((row, col + 1)),
+            ]
+        });
+        candidates.flatten().filter(move |&(next_row, next_col)| {
+            self.data.get(&(next_row, next_col)).is_some_and(|next| {
+                if next_row < row {
+                    next.has_lower_connection()
+                } else if next_row > row {
+                    next.has_upper_connection()
+                } else if next_col < col {
+                    next.has_right_connection()
+                } else {
+                    next.has_left_connection()
+                }
+            })
+        })
+    }
+}
+
+impl FromStr for World {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let data = HashMap::new();
+        let mut world = World {
+            data,
+            start: (0, 0),
+        };
+        let mut start = None;
+        for (row, line) in value.lines().enumerate() {
+            for (col, pipe) in line.chars().enumerate().filter(|(_, p)| *p != '.') {
+                let coords = (row, col);
+                let Some(pipe_type) = PipeType::from_char(pipe) else {
+                    bail!(
+                        "unexpected character `{}` at row {}, column {}",
+                        pipe,
+                        row + 1,
+                        col + 1
+                    );
+                };
+                if pipe == 'S' && start.replace(coords).is_some() {
+                    bail!("more than one start tile");
+                }
+                world.data.insert(coords, Node::new(pipe_type));
+            }
+        }
+        let Some(start) = start else {
+            bail!("no start tile");
+        };
+        world.start = start;
+
+        Ok(world)
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    connections: u8,
+}
+
+impl Node {
+    fn new(pipe_type: PipeType) -> Self {
+        Node {
+            connections: Node::set_connections(pipe_type),
+        }
+    }
+
+    fn has_upper_connection(&self) -> bool {
+        self.connections & 1 == 1
+    }
+
+    fn has_left_connection(&self) -> bool {
+        self.connections >> 1 & 1 == 1
+    }
+
+    fn has_lower_connection(&self) -> bool {
+        self.connections >> 2 & 1 == 1
+    }
+
+    fn has_right_connection(&self) -> bool {
+        self.connections >> 3 & 1 == 1
+    }
+
+    fn set_connections(pipe_type: PipeType) -> u8 {
+        let mut connections = u8::default();
+        match pipe_type {
+            PipeType::Horizontal | PipeType::Start => {
+                connections |= 1 << 1;
+                connections |= 1 << 3;
+            }
+            PipeType::Vertical => {
+                connections |= 1;
+                connections |= 1 << 2;
+            }
+            PipeType::NtoE => {
+                connections |= 1;
+                connections |= 1 << 3;
+            }
+            PipeType::NtoW => {
+                connections |= 1;
+                connections |= 1 << 1;
+            }
+            PipeType::StoE => {
+                connections |= 1 << 2;
+                connections |= 1 << 3;
+            }
+            PipeType::StoW => {
+                connections |= 1 << 2;
+                connections |= 1 << 1;
+            }
+            _ => (),
+        }
+        connections
+    }
+}
+
+enum PipeType {
+    Vertical,
+    Horizontal,
+    NtoE,
+    NtoW,
+    StoE,
+    StoW,
+    Start,
+    None,
+}
+
+impl PipeType {
+    fn from_char(value: char) -> Option<Self> {
+        match value {
+            '|' => Some(PipeType::Vertical),
+            '-' => Some(PipeType::Horizontal),
+            'L' => Some(PipeType::NtoE),
+            'J' => Some(PipeType::NtoW),
+            '7' => Some(PipeType::StoW),
+            'F' => Some(PipeType::StoE),
+            'S' => Some(PipeType::Start),
+            '.' => Some(PipeType::None),
+            _ => None,
+        }
+    }
+}
+
+fn interior_area(vertices: &[(usize, usize)]) -> Result<i64> {
+    let vertices: Vec<(i64, i64)> = vertices
+        .iter()
+        .map(|&(row, col)| (row as i64, col as i64))
+        .collect();
+    math::interior_points(&vertices)
+}
+
+/// Generates a field about `size` tiles square holding one winding loop among
+/// junk pipes.
+///
+/// The loop traces the outline of a random spanning tree over 2x2 blocks of
+/// corners, which are then spaced out so the loop encloses tiles. The start
+/// sits on a horizontal run of the loop, which `World::explore` relies on.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let blocks = (size / 4).max(1);
+
+    // right[r][c] joins block (r, c) to (r, c + 1), down[r][c] to (r + 1, c)
+    let mut right = vec![vec![false; blocks]; blocks];
+    let mut down = vec![vec![false; blocks]; blocks];
+    let mut seen = vec![vec![false; blocks]; blocks];
+    let mut stack = vec![(0, 0)];
+    seen[0][0] = true;
+    while let Some(&(r, c)) = stack.last() {
+        let mut options = Vec::new();
+        if r > 0 && !seen[r - 1][c] {
+            options.push((r - 1, c));
+        }
+        if c > 0 && !seen[r][c - 1] {
+            options.push((r, c - 1));
+        }
+        if r + 1 < blocks && !seen[r + 1][c] {
+            options.push((r + 1, c));
+        }
+        if c + 1 < blocks && !seen[r][c + 1] {
+            options.push((r, c + 1));
+        }
+        match options.choose(rng) {
+            Some(&(next_r, next_c)) => {
+                match (next_r.cmp(&r), next_c.cmp(&c)) {
+                    (std::cmp::Ordering::Less, _) => down[next_r][c] = true,
+                    (std::cmp::Ordering::Greater, _) => down[r][c] = true,
+                    (_, std::cmp::Ordering::Less) => right[r][next_c] = true,
+                    _ => right[r][c] = true,
+                }
+                seen[next_r][next_c] = true;
+                stack.push((next_r, next_c));
+            }
+            None => _ = stack.pop(),
+        }
+    }
+
+    // Every block contributes a 2x2 square of corners. Where the tree joins
+    // two blocks their facing sides are swapped for a pair of bridges, which
+    // merges all the squares into a single loop.
+    let corners = blocks * 2;
+    let side = corners * 2 - 1;
+    let mut grid = vec![vec![None; side]; side];
+    let mut starts = Vec::new();
+    for y in 0..corners {
+        for x in 0..corners {
+            let (block_r, block_c) = (y / 2, x / 2);
+            let (top, left) = (y % 2 == 0, x % 2 == 0);
+            let bridge_vertical = if top {
+                block_r > 0 && down[block_r - 1][block_c]
+            } else {
+                down[block_r][block_c]
+            };
+            let bridge_horizontal = if left {
+                block_c > 0 && right[block_r][block_c - 1]
+            } else {
+                right[block_r][block_c]
+            };
+            // Each corner has one link that either crosses a vertical bridge or
+            // runs along its row of the block, and one that either crosses a
+            // horizontal bridge or runs along its column.
+            let goes_up = (bridge_vertical && top) || (!bridge_horizontal && !top);
+            let goes_down = (bridge_vertical && !top) || (!bridge_horizontal && top);
+            let goes_left = (bridge_horizontal && left) || (!bridge_vertical && !left);
+            let goes_right = (bridge_horizontal && !left) || (!bridge_vertical && left);
+            let pipe = match (goes_up, goes_down, goes_left, goes_right) {
+                (true, true, _, _) => '|',
+                (_, _, true, true) => '-',
+                (true, _, true, _) => 'J',
+                (true, _, _, true) => 'L',
+                (_, true, true, _) => '7',
+                _ => 'F',
+            };
+            grid[y * 2][x * 2] = Some(pipe);
+            if goes_right {
+                grid[y * 2][x * 2 + 1] = Some('-');
+                starts.push((y * 2, x * 2 + 1));
+            }
+            if goes_down {
+                grid[y * 2 + 1][x * 2] = Some('|');
+            }
+        }
+    }
+    let &(start_r, start_c) = starts.choose(rng).unwrap();
+    grid[start_r][start_c] = Some('S');
+
+    let mut output = String::new();
+    for row in grid {
+        for tile in row {
+            output.push(tile.unwrap_or_else(|| {
+                if rng.gen_bool(0.3) {
+                    *b"|-LJ7F".choose(rng).unwrap() as char
+                } else {
+                    '.'
+                }
+            }));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enclosed() {
+        let input = "...........
+.F-S-----7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+        let world: World = input.parse().unwrap();
+        let boundary = world.explore();
+        assert_eq!(interior_area(&boundary).unwrap(), 4);
+        assert_eq!(world.enclosed(&boundary).len(), 4);
+
+        let ctx = Context {
+            frames: Frames::enabled(),
+            ..Context::default()
+        };
+        solve(input, &ctx).unwrap();
+        let frames = ctx.frames.into_frames();
+        let last = &frames.last().unwrap().grid;
+        assert_eq!(last.get(6, 2), Rgb::WHITE);
+        assert_eq!(last.get(3, 3), Rgb::BLACK);
+    }
+
+    #[test]
+    fn test_has_connections() {
+        let node = Node::new(PipeType::Vertical);
+
+        assert!(node.has_upper_connection());
+        assert!(!node.has_left_connection());
+        assert!(node.has_lower_connection());
+        assert!(!node.has_right_connection());
+
+        let node_b = Node::new(PipeType::Horizontal);
+
+        assert!(!node_b.has_upper_connection());
+        assert!(node_b.has_left_connection());
+        assert!(!node_b.has_lower_connection());
+        assert!(node_b.has_right_connection());
+
+        let node_c = Node::new(PipeType::NtoE);
+        assert!(node_c.has_upper_connection());
+        assert!(!node_c.has_left_connection());
+        assert!(!node_c.has_lower_connection());
+        assert!(node_c.has_right_connection());
+
+        let node_d = Node::new(PipeType::StoW);
+        assert!(!node_d.has_upper_connection());
+        assert!(node_d.has_left_connection());
+        assert!(node_d.has_lower_connection());
+        assert!(!node_d.has_right_connection());
+    }
+}
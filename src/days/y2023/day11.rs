@@ -0,0 +1,262 @@
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+    days::{Answers, Context},
+    viz::{Grid, Rgb},
+};
+use anyhow::{anyhow, bail, Result};
+use rand::{rngs::StdRng, Rng};
+
+const EXPANSION: usize = 1000000;
+
+pub fn solve(input: &str, ctx: &Context) -> Result<Answers> {
+    let mut universe: Universe = input.parse()?;
+    ctx.frames.record(1, || universe.draw());
+    universe.expand(2);
+    ctx.frames.record(1, || universe.draw());
+    let universe = universe;
+    let galaxy_coords = universe.get_galaxy_coords();
+    let part_1 = galaxy_distances(galaxy_coords);
+
+    let expansion = ctx.params.get_or("expansion", EXPANSION)?;
+    if expansion == 0 {
+        bail!("expansion must be at least 1");
+    }
+    let mut universe: Universe = input.parse()?;
+    universe.expand(expansion);
+    Ok(Answers::default()
+        .with_part_1(part_1)
+        .with_part_2(galaxy_distances(universe.get_galaxy_coords())))
+}
+
+pub fn parse(input: &str) -> Result<()> {
+    input.parse::<Universe>().map(drop)
+}
+
+fn galaxy_distances(coords: &HashSet<(usize, usize)>) -> usize {
+    // Manhattan distance splits into independent row and column sums. Once
+    // sorted, the i-th value is added for the i values below it and
+    // subtracted for the ones above it.
+    let axis_sum = |mut values: Vec<usize>| {
+        values.sort_unstable();
+        let n = values.len() as i128;
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| value as i128 * (2 * i as i128 - n + 1))
+            .sum::<i128>() as usize
+    };
+    axis_sum(coords.iter().map(|c| c.0).collect()) + axis_sum(coords.iter().map(|c| c.1).collect())
+}
+
+#[derive(Debug)]
+struct Universe {
+    galaxies: HashSet<(usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+impl FromStr for Universe {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let height = value.lines().count();
+        let width = value
+            .lines()
+            .next()
+            .ok_or_else(|| anyhow!("empty image"))?
+            .chars()
+            .count();
+        let mut galaxies = HashSet::new();
+        for (row, line) in value.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '#' => _ = galaxies.insert((row, col)),
+                    '.' => (),
+                    _ => bail!(
+                        "unexpected character `{}` at row {}, column {}",
+                        c,
+                        row + 1,
+                        col + 1
+                    ),
+                }
+            }
+        }
+
+        Ok(Universe {
+            galaxies,
+            width,
+            height,
+        })
+    }
+}
+
+impl Universe {
+    fn expand_rows(&mut self, factor: usize) {
+        let mut i = 0;
+        while i < self.height {
+            // determine if there are any galaxies on this row, if so
+            // increment to next row and continue
+            if self.galaxies.iter().any(|(row, _)| *row == i) {
+                i += 1;
+            } else {
+                // increment the row value of all galaxies with .0 == i by factor
+                let mut updated_galaxies = HashSet::new();
+
+                self.galaxies.clone().iter().for_each(|(row, col)| {
+                    if *row > i {
+                        self.galaxies.remove(&(*row, *col));
+                        updated_galaxies.insert((*row + (factor - 1), *col));
+                    }
+                });
+                self.galaxies = self
+                    .galaxies
+                    .clone()
+                    .into_iter()
+                    .chain(updated_galaxies)
+                    .collect();
+                i += factor;
+                self.height += factor - 1;
+            }
+        }
+    }
+
+    fn expand_cols(&mut self, factor: usize) {
+        let mut i = 0;
+        while i < self.width {
+            // determine if there are any galaxies on this row, if so
+            // increment to next row and continue
+            if self.galaxies.iter().any(|(_, col)| *col == i) {
+                i += 1;
+            } else {
+                // increment the row value of all galaxies with .x == i by factor
+                let mut updated_galaxies = HashSet::new();
+                self.galaxies.clone().iter().for_each(|(row, col)| {
+                    if *col > i {
+                        self.galaxies.remove(&(*row, *col));
+                        updated_galaxies.insert((*row, *col + (factor - 1)));
+                    }
+                });
+                self.galaxies = self
+                    .galaxies
+                    .clone()
+                    .into_iter()
+                    .chain(updated_galaxies)
+                    .collect();
+                i += factor;
+                self.width += factor - 1;
+            }
+        }
+    }
+
+    fn expand(&mut self, factor: usize) {
+        self.expand_rows(factor);
+        self.expand_cols(factor);
+    }
+
+    fn get_galaxy_coords(&self) -> &HashSet<(usize, usize)> {
+        &self.galaxies
+    }
+
+    fn draw(&self) -> Grid {
+        let mut grid = Grid::new(self.width, self.height, Rgb::BLACK);
+        for &(row, col) in self.galaxies.iter() {
+            grid.set(row, col, Rgb::WHITE);
+        }
+        grid
+    }
+}
+
+/// Generates a `size` by `size` image in which roughly one cell in twenty is
+/// a galaxy.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut output = String::new();
+    for _ in 0..size.max(1) {
+        for _ in 0..size.max(1) {
+            output.push(if rng.gen_bool(0.05) { '#' } else { '.' });
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{differential, util};
+    extern crate test;
+    use test::Bencher;
+
+    #[test]
+    fn basic_test() {
+        let universe = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+        let mut universe: Universe = universe.parse().unwrap();
+        universe.expand(2);
+        assert_eq!(374, galaxy_distances(universe.get_galaxy_coords()));
+    }
+
+    fn cartesian_distance(coord: (usize, usize), other: (usize, usize)) -> usize {
+        coord.0.abs_diff(other.0) + coord.1.abs_diff(other.1)
+    }
+
+    fn galaxy_distances_reference(coords: &HashSet<(usize, usize)>) -> usize {
+        let mut processed: HashSet<(usize, usize)> = HashSet::new();
+        let mut distance_sum = 0;
+        for galaxy_1 in coords.iter() {
+            for galaxy_2 in coords.iter() {
+                if galaxy_1 != galaxy_2 && !processed.contains(galaxy_2) {
+                    distance_sum += cartesian_distance(*galaxy_1, *galaxy_2);
+                }
+            }
+            processed.insert(*galaxy_1);
+        }
+
+        distance_sum
+    }
+
+    #[test]
+    fn test_galaxy_distances_matches_reference() {
+        let expanded = |input: &str| {
+            let mut universe: Universe = input.parse().unwrap();
+            universe.expand(EXPANSION);
+            universe
+        };
+        differential::check(
+            2023,
+            11,
+            &[1, 5, 20, 60],
+            |input| galaxy_distances_reference(expanded(input).get_galaxy_coords()),
+            |input| galaxy_distances(expanded(input).get_galaxy_coords()),
+        );
+    }
+
+    #[bench]
+    fn bench_part_1(b: &mut Bencher) {
+        let raw_input = util::bench_input(2023, 11);
+        b.iter(|| {
+            let mut universe: Universe = raw_input.parse().unwrap();
+            universe.expand(2);
+            galaxy_distances(universe.get_galaxy_coords());
+        })
+    }
+
+    #[bench]
+    fn bench_part_2(b: &mut Bencher) {
+        let raw_input = util::bench_input(2023, 11);
+        b.iter(|| {
+            let mut universe: Universe = raw_input.parse().unwrap();
+            universe.expand(EXPANSION);
+            galaxy_distances(universe.get_galaxy_coords());
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use super::Day;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+
+const YEAR: u16 = 2023;
+
+pub const DAYS: &[Day] = &[
+    Day::new(YEAR, 1, day01::solve, day01::parse, day01::generate).with_stream(day01::stream),
+    Day::new(YEAR, 2, day02::solve, day02::parse, day02::generate)
+        .with_normalize(day02::normalize)
+        .with_report(day02::report),
+    Day::new(YEAR, 3, day03::solve, day03::parse, day03::generate),
+    Day::new(YEAR, 4, day04::solve, day04::parse, day04::generate),
+    Day::new(YEAR, 5, day05::solve, day05::parse, day05::generate),
+    Day::new(YEAR, 6, day06::solve, day06::parse, day06::generate),
+    Day::new(YEAR, 7, day07::solve, day07::parse, day07::generate),
+    Day::new(YEAR, 8, day08::solve, day08::parse, day08::generate),
+    Day::new(YEAR, 9, day09::solve, day09::parse, day09::generate),
+    Day::new(YEAR, 10, day10::solve, day10::parse, day10::generate),
+    Day::new(YEAR, 11, day11::solve, day11::parse, day11::generate),
+];
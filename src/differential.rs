@@ -0,0 +1,167 @@
+//! Differential testing of optimized solvers against slow reference
+//! implementations.
+//!
+//! A day's test module keeps the straightforward version of anything it
+//! optimizes and checks that both agree:
+//!
+//! ```ignore
+//! #[test]
+//! fn test_matches_reference() {
+//!     differential::check(2023, 6, &[10, 1000], reference, optimized);
+//! }
+//! ```
+//!
+//! Both run on every real input found for the day and on generated inputs of
+//! each size. On disagreement, including only one of them panicking, the
+//! test fails with the smallest input, by lines, on which they still differ.
+
+use std::fmt::Debug;
+
+use crate::{
+    config::{self, Config},
+    days, stress, util,
+};
+
+const SEEDS: u64 = 8;
+
+pub fn check<T, R, O>(year: u16, day: u8, sizes: &[usize], reference: R, optimized: O)
+where
+    T: PartialEq + Debug,
+    R: Fn(&str) -> T,
+    O: Fn(&str) -> T,
+{
+    let differs = |input: &str| -> bool {
+        match (
+            util::catch_panic(|| reference(input)),
+            util::catch_panic(|| optimized(input)),
+        ) {
+            (Ok(expected), Ok(actual)) => expected != actual,
+            // both crashing is agreement, only one crashing is not
+            (Err(_), Err(_)) => false,
+            _ => true,
+        }
+    };
+
+    for (source, input) in real_inputs(year, day)
+        .into_iter()
+        .chain(generated_inputs(year, day, sizes))
+    {
+        if differs(&input) {
+            let minimal = shrink(&input, differs);
+            panic!(
+                "optimized implementation disagrees with reference on {}\nminimal input:\n{}\nreference: {:?}\noptimized: {:?}",
+                source,
+                minimal,
+                Outcome(util::catch_panic(|| reference(&minimal))),
+                Outcome(util::catch_panic(|| optimized(&minimal)))
+            );
+        }
+    }
+}
+
+/// What an implementation returned, or the message it panicked with.
+struct Outcome<T>(Result<T, String>);
+
+impl<T: Debug> Debug for Outcome<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Ok(value) => write!(f, "{:?}", value),
+            Err(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+fn real_inputs(year: u16, day: u8) -> Vec<(String, String)> {
+    let Ok(config) = Config::load(config::DEFAULT_PATH) else {
+        return Vec::new();
+    };
+    let mut sets = vec![(String::from("default"), config.clone())];
+    for name in config.input_sets().unwrap_or_default() {
+        sets.push((name.clone(), config.with_set(&name)));
+    }
+    sets.into_iter()
+        .filter_map(|(name, set)| {
+            util::read_input(set.input_path(year, day))
+                .ok()
+                .map(|input| (format!("the {} input", name), input))
+        })
+        .collect()
+}
+
+fn generated_inputs(year: u16, day: u8, sizes: &[usize]) -> Vec<(String, String)> {
+    let day = days::find(year, day).expect("no solution registered for this day");
+    sizes
+        .iter()
+        .flat_map(|&size| {
+            (0..SEEDS).map(move |seed| {
+                (
+                    format!("a generated input (size {}, seed {})", size, seed),
+                    stress::generate(day, size, seed),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Removes ever smaller runs of lines from `input` for as long as the
+/// implementations keep disagreeing.
+fn shrink(input: &str, differs: impl Fn(&str) -> bool) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut removed_any = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = lines[..start]
+                .iter()
+                .chain(&lines[end..])
+                .copied()
+                .collect();
+            if differs(&(candidate.join("\n") + "\n")) {
+                lines = candidate;
+                removed_any = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed_any {
+            chunk /= 2;
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink() {
+        let input = "a\nb\nxyz\nc\nd\nx\ne\n";
+        // "optimized" miscounts lines containing an x
+        let differs = |input: &str| {
+            input.lines().count() != input.lines().filter(|line| !line.contains('x')).count()
+        };
+        assert_eq!(shrink(input, differs), "x\n");
+        assert_eq!(shrink("a\nb\n", |input| input.contains('a')), "a\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "minimal input")]
+    fn test_check_reports_disagreement() {
+        check(2023, 9, &[5], |input| input.lines().count(), |_| 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "optimized: panicked: out of cheese")]
+    fn test_check_reports_one_sided_panic() {
+        check(
+            2023,
+            9,
+            &[5],
+            |input| input.lines().count(),
+            |_| -> usize { panic!("out of cheese") },
+        );
+    }
+}
@@ -1,3 +1,13 @@
-#![feature(test)]
+#![cfg_attr(test, feature(test))]
+pub mod answers;
+pub mod config;
 pub mod days;
-mod util;
+#[cfg(test)]
+mod differential;
+pub mod report;
+pub mod runner;
+pub mod serve;
+pub mod stress;
+pub mod tui;
+pub mod util;
+pub mod viz;
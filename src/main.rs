@@ -1,5 +1,255 @@
-use advent_2023::days;
+use std::{path::PathBuf, process::ExitCode, time::Duration};
 
-fn main() {
-    days::run();
+use advent::{
+    config::{self, Config, Format},
+    days, report, runner,
+    serve::{self, Limits},
+    stress, tui, util,
+    viz::{self, Output},
+};
+use anyhow::{anyhow, Context as _, Result};
+use clap::{Parser, Subcommand};
+
+/// Runs Advent of Code solutions.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Only run solutions for this year
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// Only run this day
+    #[arg(long, global = true)]
+    day: Option<u8>,
+
+    /// Project configuration file
+    #[arg(long, global = true, default_value = config::DEFAULT_PATH)]
+    config: PathBuf,
+
+    /// Directory holding `<year>/dayNN.txt` inputs
+    #[arg(long, global = true)]
+    input_dir: Option<PathBuf>,
+
+    /// Directory holding `<year>.txt` answer manifests
+    #[arg(long, global = true)]
+    answers_dir: Option<PathBuf>,
+
+    /// Named input set, read from `<input_dir>/<set>/<year>/dayNN.txt`
+    #[arg(long, global = true)]
+    set: Option<String>,
+
+    /// Output format
+    #[arg(long, global = true, value_enum)]
+    format: Option<Format>,
+
+    /// Print what the solvers note on the way to their answers
+    #[arg(long, global = true)]
+    trace: bool,
+
+    /// Solver parameter, e.g. `--param expansion=10`
+    #[arg(long = "param", global = true, value_name = "KEY=VALUE")]
+    params: Vec<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions (the default)
+    Run,
+    /// Browse, run and inspect days in an interactive terminal dashboard
+    Tui,
+    /// Run every day against every input set and report failures or wrong answers
+    Check,
+    /// Print a randomly generated input
+    Generate {
+        /// Size of the input, e.g. lines or grid side depending on the day
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Print a puzzle input rewritten in its day's canonical form
+    Normalize {
+        /// Input to rewrite, by default the day's configured input
+        file: Option<PathBuf>,
+    },
+    /// Print a day's detailed account of an input, as text or JSON with `--format`
+    Inspect {
+        /// Input to report on, by default the day's configured input
+        file: Option<PathBuf>,
+    },
+    /// Solve a day reading its input a line at a time, for inputs too large to load
+    Stream {
+        /// Input to read, by default the day's configured input
+        file: Option<PathBuf>,
+    },
+    /// Time solvers on generated inputs of increasing size
+    Stress {
+        /// Input sizes to try, smallest first
+        #[arg(long, value_delimiter = ',', default_value = "10,20,40,80,160")]
+        sizes: Vec<usize>,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Run the solutions and write a self-contained HTML report of the results
+    Report {
+        #[arg(long, default_value = "report/index.html")]
+        out: PathBuf,
+
+        /// Timings of earlier runs, added to on every report
+        #[arg(long, default_value = "report/history.jsonl")]
+        history: PathBuf,
+    },
+    /// Draw what the grid days see, as images, an animation or in the terminal
+    Viz {
+        #[arg(long, value_enum, default_value = "gif")]
+        output: Output,
+
+        /// Directory images are written to
+        #[arg(long, default_value = "viz")]
+        dir: PathBuf,
+
+        /// Pixels to a grid cell
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+
+        /// Only draw this part
+        #[arg(long)]
+        part: Option<u8>,
+    },
+    /// Serve a local HTTP API that solves posted inputs, e.g. `POST /2023/day/5`
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8023")]
+        addr: String,
+
+        /// Largest accepted input, in bytes
+        #[arg(long, default_value_t = 1024 * 1024)]
+        max_body: usize,
+
+        /// Seconds a request may spend solving before it is abandoned
+        #[arg(long, default_value_t = 10.0)]
+        time_budget: f64,
+
+        /// Most solves running at once, abandoned ones included
+        #[arg(long, default_value_t = 4)]
+        max_solves: usize,
+    },
+}
+
+impl Cli {
+    fn config(&self) -> Result<Config> {
+        let mut config = Config::load(&self.config)?;
+        if let Some(input_dir) = &self.input_dir {
+            config.input_dir = input_dir.clone();
+        }
+        if let Some(answers_dir) = &self.answers_dir {
+            config.answers_dir = answers_dir.clone();
+        }
+        if let Some(format) = self.format {
+            config.format = format;
+        }
+        for param in self.params.iter() {
+            config.set_param(param)?;
+        }
+        if let Some(set) = &self.set {
+            config = config.with_set(set);
+        }
+        Ok(config)
+    }
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let config = cli.config()?;
+    match cli.command.as_ref().unwrap_or(&Command::Run) {
+        Command::Run => runner::run(&config, cli.year, cli.day, cli.trace)?,
+        Command::Tui => tui::run(&config, cli.year, cli.day)?,
+        Command::Check => {
+            if !runner::check(&config, cli.year, cli.day)? {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Generate { size, seed } => {
+            let (Some(year), Some(day)) = (cli.year, cli.day) else {
+                return Err(anyhow!("generate needs both --year and --day"));
+            };
+            let day = days::find(year, day)
+                .ok_or_else(|| anyhow!("no solution registered for {} day {}", year, day))?;
+            print!("{}", stress::generate(day, *size, *seed));
+        }
+        Command::Normalize { file } => {
+            let (Some(year), Some(day)) = (cli.year, cli.day) else {
+                return Err(anyhow!("normalize needs both --year and --day"));
+            };
+            let day = days::find(year, day)
+                .ok_or_else(|| anyhow!("no solution registered for {} day {}", year, day))?;
+            let normalize = day
+                .normalize
+                .ok_or_else(|| anyhow!("{} day {} has no canonical form", day.year, day.day))?;
+            let path = file
+                .clone()
+                .unwrap_or_else(|| config.input_path(day.year, day.day));
+            let input =
+                util::read_input(&path).with_context(|| format!("reading {}", path.display()))?;
+            print!("{}", normalize(&input)?);
+        }
+        Command::Inspect { file } => {
+            let (Some(year), Some(day)) = (cli.year, cli.day) else {
+                return Err(anyhow!("inspect needs both --year and --day"));
+            };
+            let day = days::find(year, day)
+                .ok_or_else(|| anyhow!("no solution registered for {} day {}", year, day))?;
+            let report = day
+                .report
+                .ok_or_else(|| anyhow!("{} day {} has no report", day.year, day.day))?;
+            let path = file
+                .clone()
+                .unwrap_or_else(|| config.input_path(day.year, day.day));
+            let input =
+                util::read_input(&path).with_context(|| format!("reading {}", path.display()))?;
+            print!(
+                "{}",
+                report(&input, &config.params(day.year, day.day), config.format)?
+            );
+        }
+        Command::Stream { file } => {
+            let (Some(year), Some(day)) = (cli.year, cli.day) else {
+                return Err(anyhow!("stream needs both --year and --day"));
+            };
+            runner::stream(&config, year, day, file.as_deref())?
+        }
+        Command::Stress { sizes, seed } => stress::run(&config, cli.year, cli.day, sizes, *seed)?,
+        Command::Report { out, history } => report::run(&config, cli.year, cli.day, out, history)?,
+        Command::Viz {
+            output,
+            dir,
+            scale,
+            part,
+        } => {
+            let options = viz::Options {
+                output: *output,
+                dir: dir.clone(),
+                scale: *scale,
+                part: *part,
+            };
+            viz::run(&config, cli.year, cli.day, &options)?
+        }
+        Command::Serve {
+            addr,
+            max_body,
+            time_budget,
+            max_solves,
+        } => {
+            let limits = Limits {
+                max_body: *max_body,
+                time_budget: Duration::try_from_secs_f64(*time_budget)?,
+                max_solves: *max_solves,
+            };
+            serve::run(&config, addr, limits)?
+        }
+    }
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,384 @@
+//! A self-contained HTML page summing up a run: every day's answers and
+//! verdicts, parse and solve timings against earlier runs, and a picture of
+//! each grid day.
+
+use std::{
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context as _, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::{Manifest, Verdict},
+    config::Config,
+    days::Trace,
+    runner::{self, DayResult, Status},
+    viz::{self, Frames},
+};
+
+/// How many runs the timing sparklines reach back over.
+const HISTORY: usize = 30;
+/// Longest side of an embedded render, in pixels.
+const RENDER_SIZE: usize = 480;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+.correct { color: #2a7d2a; }
+.unverified { color: #a07800; }
+.wrong, .error { color: #c0392b; }
+svg.spark { vertical-align: middle; margin-left: 0.5em; color: #268bd2; }
+figure { display: inline-block; margin: 0 1em 1em 0; }
+img { image-rendering: pixelated; }
+";
+
+/// One run's timings, as kept in the history file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Run {
+    /// Seconds since the Unix epoch.
+    started: u64,
+    days: Vec<Timing>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Timing {
+    year: u16,
+    day: u8,
+    /// Seconds spent parsing.
+    parse: f64,
+    /// Seconds spent solving.
+    solve: f64,
+}
+
+impl Run {
+    fn of(results: &[DayResult]) -> Self {
+        Run {
+            started: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            days: results
+                .iter()
+                .filter(|result| result.error.is_none())
+                .map(|result| Timing {
+                    year: result.year,
+                    day: result.day,
+                    parse: result.parse.as_secs_f64(),
+                    solve: result.elapsed.as_secs_f64(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Runs the selected days, adds their timings to the history file and
+/// writes the report to `out`.
+pub fn run(
+    config: &Config,
+    year: Option<u16>,
+    day: Option<u8>,
+    out: &Path,
+    history: &Path,
+) -> Result<()> {
+    let mut results = Vec::new();
+    let mut manifest: Option<(u16, Manifest)> = None;
+    for day in runner::select(year, day)? {
+        if manifest.as_ref().map(|(year, _)| *year) != Some(day.year) {
+            manifest = Some((day.year, Manifest::load(config.answers_path(day.year))?));
+        }
+        let (_, answers) = manifest.as_ref().unwrap();
+        // Keeping frames slows the grid days down, so they are drawn in a
+        // second run rather than in the one that is timed.
+        let mut result = runner::run_day(config, day, answers, Trace::default(), Frames::default());
+        if result.error.is_none() {
+            result.frames = viz::draw(config, day).unwrap_or_default();
+        }
+        results.push(result);
+    }
+
+    let mut runs = load_history(history)?;
+    let run = Run::of(&results);
+    append_history(history, &run)?;
+    runs.push(run);
+
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    fs::write(out, render(&results, &runs)?)
+        .with_context(|| format!("writing {}", out.display()))?;
+    println!("wrote {}", out.display());
+    Ok(())
+}
+
+/// Loads earlier runs, oldest first. A missing file has no runs in it.
+fn load_history(path: &Path) -> Result<Vec<Run>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("{}: line {}", path.display(), n + 1))
+        })
+        .collect()
+}
+
+fn append_history(path: &Path, run: &Run) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+fn render(results: &[DayResult], runs: &[Run]) -> Result<String> {
+    let mut html = String::new();
+    writeln!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code run</title>\n<style>{}</style>\n</head>\n<body>\n<h1>Advent of Code run</h1>",
+        STYLE
+    )?;
+
+    let correct = results
+        .iter()
+        .filter(|result| result.status() == Status::Correct)
+        .count();
+    writeln!(
+        html,
+        "<p>{} days run, {} correct. Solve time {}.</p>",
+        results.len(),
+        correct,
+        seconds(results.iter().map(|result| result.elapsed).sum())
+    )?;
+
+    writeln!(
+        html,
+        "<table>\n<tr><th>day</th><th>status</th><th>part 1</th><th>part 2</th><th>parse</th><th>solve</th></tr>"
+    )?;
+    let recent = &runs[runs.len().saturating_sub(HISTORY)..];
+    for result in results {
+        let status = result.status();
+        write!(
+            html,
+            "<tr><td>{} day {:02}</td><td class=\"{}\">{}</td>",
+            result.year, result.day, status, status
+        )?;
+        if let Some(error) = &result.error {
+            write!(
+                html,
+                "<td colspan=\"2\" class=\"error\">{}</td>",
+                escape(error)
+            )?;
+        } else {
+            for part in 1..=2 {
+                match result.parts.iter().find(|p| p.part == part) {
+                    Some(part) => write!(
+                        html,
+                        "<td class=\"{}\" title=\"{}\">{}</td>",
+                        verdict_class(&part.verdict),
+                        escape(&part.verdict.to_string()),
+                        escape(&part.answer)
+                    )?,
+                    None => html.push_str("<td></td>"),
+                }
+            }
+        }
+
+        let history: Vec<&Timing> = recent
+            .iter()
+            .filter_map(|run| {
+                run.days
+                    .iter()
+                    .find(|timing| timing.year == result.year && timing.day == result.day)
+            })
+            .collect();
+        let parse: Vec<f64> = history.iter().map(|timing| timing.parse).collect();
+        let solve: Vec<f64> = history.iter().map(|timing| timing.solve).collect();
+        writeln!(
+            html,
+            "<td class=\"time\">{}{}</td><td class=\"time\">{}{}</td></tr>",
+            seconds(result.parse),
+            sparkline(&parse),
+            seconds(result.elapsed),
+            sparkline(&solve)
+        )?;
+    }
+    html.push_str("</table>\n");
+
+    for result in results.iter().filter(|result| !result.frames.is_empty()) {
+        writeln!(html, "<h2>{} day {:02}</h2>", result.year, result.day)?;
+        for part in 1..=2 {
+            // the last frame shows where the solver ended up
+            let Some(frame) = result.frames.iter().rev().find(|frame| frame.part == part) else {
+                continue;
+            };
+            let grid = &frame.grid;
+            let scale = (RENDER_SIZE / grid.width().max(grid.height()).max(1)).max(1);
+            let mut png = Vec::new();
+            viz::write_png(grid, scale, &mut png)?;
+            writeln!(
+                html,
+                "<figure><img alt=\"part {}\" src=\"data:image/png;base64,{}\"><figcaption>part {}</figcaption></figure>",
+                part,
+                STANDARD.encode(png),
+                part
+            )?;
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    Ok(html)
+}
+
+/// An inline SVG line through `values`, scaled to fit, or nothing when there
+/// is no history to show.
+fn sparkline(values: &[f64]) -> String {
+    const WIDTH: f64 = 80.0;
+    const HEIGHT: f64 = 16.0;
+    if values.len() < 2 {
+        return String::new();
+    }
+    let max = values.iter().copied().fold(f64::MIN_POSITIVE, f64::max);
+    let step = WIDTH / (values.len() - 1) as f64;
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(n, value)| {
+            format!(
+                "{:.1},{:.1}",
+                n as f64 * step,
+                HEIGHT - value / max * (HEIGHT - 1.0)
+            )
+        })
+        .collect();
+    format!(
+        "<svg class=\"spark\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\"><polyline fill=\"none\" stroke=\"currentColor\" points=\"{}\"/></svg>",
+        WIDTH,
+        HEIGHT + 1.0,
+        WIDTH,
+        HEIGHT + 1.0,
+        points.join(" ")
+    )
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+fn verdict_class(verdict: &Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong { .. } => "wrong",
+        Verdict::Unverified => "unverified",
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runner::PartResult,
+        viz::{Frame, Grid, Rgb},
+    };
+
+    fn result(day: u8, answer: &str, verdict: Verdict) -> DayResult {
+        DayResult {
+            year: 2023,
+            day,
+            parts: vec![PartResult {
+                part: 1,
+                answer: answer.to_owned(),
+                verdict,
+            }],
+            extras: Vec::new(),
+            parse: Duration::from_micros(20),
+            elapsed: Duration::from_millis(3),
+            trace: Vec::new(),
+            frames: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut grid_day = result(10, "6", Verdict::Correct);
+        grid_day.frames.push(Frame {
+            part: 2,
+            grid: Grid::new(3, 3, Rgb::WHITE),
+        });
+        let results = vec![
+            result(
+                1,
+                "<b>",
+                Verdict::Wrong {
+                    expected: String::from("3"),
+                },
+            ),
+            grid_day,
+        ];
+        let earlier = Run {
+            started: 0,
+            days: vec![Timing {
+                year: 2023,
+                day: 10,
+                parse: 0.001,
+                solve: 0.01,
+            }],
+        };
+        let html = render(&results, &[earlier, Run::of(&results)]).unwrap();
+
+        assert!(html.contains("<td class=\"wrong\" title=\"wrong, expected 3\">&lt;b&gt;</td>"));
+        assert!(html.contains("<td class=\"correct\" title=\"correct\">6</td>"));
+        // only day 10 has an earlier run to draw a line through
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert_eq!(html.matches("data:image/png;base64,").count(), 1);
+        assert!(html.contains("<h2>2023 day 10</h2>"));
+    }
+
+    #[test]
+    fn test_history() {
+        let path = std::env::temp_dir()
+            .join(format!("advent-report-{}", std::process::id()))
+            .join("history.jsonl");
+        assert!(load_history(&path).unwrap().is_empty());
+
+        let first = Run::of(&[result(1, "142", Verdict::Unverified)]);
+        let second = Run::of(&[result(2, "8", Verdict::Unverified)]);
+        append_history(&path, &first).unwrap();
+        append_history(&path, &second).unwrap();
+        assert_eq!(load_history(&path).unwrap(), vec![first, second]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
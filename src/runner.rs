@@ -0,0 +1,335 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::BufReader,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use serde::Serialize;
+
+use crate::{
+    answers::{Manifest, Verdict},
+    config::{Config, Format},
+    days::{self, Answers, Context, Day, Extra, Trace, TraceLine},
+    util,
+    viz::{Frame, Frames},
+};
+
+/// The outcome of running one day, as printed or serialized by the runner.
+#[derive(Debug, Serialize)]
+pub struct DayResult {
+    pub year: u16,
+    pub day: u8,
+    pub parts: Vec<PartResult>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<Extra>,
+    /// Time spent parsing the input alone.
+    #[serde(serialize_with = "util::as_secs")]
+    pub parse: Duration,
+    /// Time spent in the solver, not counting reading the input.
+    #[serde(serialize_with = "util::as_secs")]
+    pub elapsed: Duration,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<TraceLine>,
+    #[serde(skip)]
+    pub frames: Vec<Frame>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A day's overall outcome, as bad as its worst part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    Correct,
+    Unverified,
+    Wrong,
+    Error,
+}
+
+impl DayResult {
+    /// A result for `day` with nothing recorded yet.
+    pub(crate) fn new(day: &Day) -> Self {
+        DayResult {
+            year: day.year,
+            day: day.day,
+            parts: Vec::new(),
+            extras: Vec::new(),
+            parse: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            trace: Vec::new(),
+            frames: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        if self.error.is_some() {
+            return Status::Error;
+        }
+        self.parts
+            .iter()
+            .map(|part| match part.verdict {
+                Verdict::Correct => Status::Correct,
+                Verdict::Wrong { .. } => Status::Wrong,
+                Verdict::Unverified => Status::Unverified,
+            })
+            .max()
+            .unwrap_or(Status::Unverified)
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Correct => write!(f, "correct"),
+            Status::Unverified => write!(f, "unverified"),
+            Status::Wrong => write!(f, "wrong"),
+            Status::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+}
+
+/// Runs every registered day matching the filters and reports the answers,
+/// checked against the year's answer manifest when one exists, along with
+/// what the solvers noted on the way when `trace` is set.
+pub fn run(config: &Config, year: Option<u16>, day: Option<u8>, trace: bool) -> Result<()> {
+    let selected = select(year, day)?;
+
+    let mut results = Vec::new();
+    let mut manifest: Option<(u16, Manifest)> = None;
+    for day in selected {
+        if manifest.as_ref().map(|(year, _)| *year) != Some(day.year) {
+            manifest = Some((day.year, Manifest::load(config.answers_path(day.year))?));
+        }
+        let (_, answers) = manifest.as_ref().unwrap();
+
+        let trace = if trace {
+            Trace::enabled()
+        } else {
+            Trace::default()
+        };
+        let result = run_day(config, day, answers, trace, Frames::default());
+        match config.format {
+            Format::Text => print_result(&result),
+            Format::Json => results.push(result),
+        }
+    }
+
+    if config.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    }
+    Ok(())
+}
+
+/// A day in some input set whose solver failed or disagreed with the set's
+/// recorded answers.
+#[derive(Debug, Serialize)]
+pub struct Problem {
+    pub set: String,
+    #[serde(flatten)]
+    pub result: DayResult,
+}
+
+/// Runs the selected days against the default inputs and every named input
+/// set, reporting each one where a solver fails or gives a wrong answer.
+/// Days without an input in a set are skipped. Returns whether every run
+/// succeeded.
+pub fn check(config: &Config, year: Option<u16>, day: Option<u8>) -> Result<bool> {
+    let selected = select(year, day)?;
+    let mut sets = vec![(String::from("default"), config.clone())];
+    for name in config.input_sets()? {
+        sets.push((name.clone(), config.with_set(&name)));
+    }
+
+    let mut problems = Vec::new();
+    for (name, set_config) in sets.iter() {
+        let mut ran = 0;
+        let mut manifest: Option<(u16, Manifest)> = None;
+        for day in selected.iter() {
+            if !set_config.input_path(day.year, day.day).exists() {
+                continue;
+            }
+            if manifest.as_ref().map(|(year, _)| *year) != Some(day.year) {
+                manifest = Some((day.year, Manifest::load(set_config.answers_path(day.year))?));
+            }
+            let (_, answers) = manifest.as_ref().unwrap();
+
+            ran += 1;
+            let result = run_day(
+                set_config,
+                day,
+                answers,
+                Trace::default(),
+                Frames::default(),
+            );
+            if result.status() >= Status::Wrong {
+                problems.push(Problem {
+                    set: name.clone(),
+                    result,
+                });
+            }
+        }
+        if config.format == Format::Text && ran > 0 {
+            let failed = problems.iter().filter(|p| p.set == *name).count();
+            println!("{}: {} days run, {} with problems", name, ran, failed);
+        }
+    }
+
+    match config.format {
+        Format::Text => {
+            for problem in problems.iter() {
+                print!("[{}] ", problem.set);
+                print_result(&problem.result);
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&problems)?),
+    }
+    Ok(problems.is_empty())
+}
+
+/// Registered days matching the filters, or an error if there are none.
+pub(crate) fn select(year: Option<u16>, day: Option<u8>) -> Result<Vec<&'static Day>> {
+    let selected = days::select(year, day);
+    if selected.is_empty() {
+        return Err(anyhow!(
+            "no solutions registered for the requested year/day"
+        ));
+    }
+    Ok(selected)
+}
+
+/// Solves one day from its input file, keeping whatever the solver notes in
+/// `trace` and draws in `frames`. When tracing, the solver still runs after
+/// the input fails to parse, so its notes can show where it went wrong.
+pub(crate) fn run_day(
+    config: &Config,
+    day: &Day,
+    manifest: &Manifest,
+    trace: Trace,
+    frames: Frames,
+) -> DayResult {
+    let mut result = DayResult::new(day);
+    let path = config.input_path(day.year, day.day);
+    let input = match util::read_input(&path) {
+        Ok(input) => input,
+        Err(e) => {
+            result.error = Some(format!("reading {}: {}", path.display(), e));
+            return result;
+        }
+    };
+    let ctx = Context {
+        params: config.params(day.year, day.day),
+        trace,
+        frames,
+    };
+
+    let start = Instant::now();
+    let parsed = util::catch_panic(|| (day.parse)(&input))
+        .unwrap_or_else(|message| Err(anyhow!("parser panicked: {}", message)));
+    result.parse = start.elapsed();
+    if let Err(e) = parsed {
+        result.error = Some(format!("{:#}", e));
+        if ctx.trace.is_enabled() {
+            let _ = solve_input(day, &input, &ctx);
+            result.trace = ctx.trace.into_lines();
+        }
+        return result;
+    }
+
+    let start = Instant::now();
+    let answers = solve_input(day, &input, &ctx);
+    result.elapsed = start.elapsed();
+    result.trace = ctx.trace.into_lines();
+    result.frames = ctx.frames.into_frames();
+    record(&mut result, answers, manifest);
+    result
+}
+
+/// Solves one day from `file`, by default its input file, reading it a line
+/// at a time rather than all at once, and reports the answers like `run`.
+pub fn stream(config: &Config, year: u16, day: u8, file: Option<&Path>) -> Result<()> {
+    let day = days::find(year, day)
+        .ok_or_else(|| anyhow!("no solution registered for {} day {}", year, day))?;
+    let stream = day
+        .stream
+        .ok_or_else(|| anyhow!("{} day {} can't solve a streamed input", day.year, day.day))?;
+    let path = file.map_or_else(|| config.input_path(day.year, day.day), Path::to_path_buf);
+    let mut reader =
+        BufReader::new(File::open(&path).with_context(|| format!("opening {}", path.display()))?);
+    let manifest = Manifest::load(config.answers_path(day.year))?;
+    let ctx = Context {
+        params: config.params(day.year, day.day),
+        ..Context::default()
+    };
+
+    let mut result = DayResult::new(day);
+    let start = Instant::now();
+    let answers = util::catch_panic(|| stream(&mut reader, &ctx))
+        .unwrap_or_else(|message| Err(anyhow!("solver panicked: {}", message)));
+    result.elapsed = start.elapsed();
+    record(&mut result, answers, &manifest);
+    match config.format {
+        Format::Text => print_result(&result),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+    }
+    Ok(())
+}
+
+/// Adds a solver's answers to `result`, checked against `manifest`, or its
+/// error.
+fn record(result: &mut DayResult, answers: Result<Answers>, manifest: &Manifest) {
+    match answers {
+        Ok(answers) => {
+            for part in 1..=2 {
+                if let Some(answer) = answers.part(part) {
+                    result.parts.push(PartResult {
+                        part,
+                        answer: answer.to_owned(),
+                        verdict: manifest.check(result.day, part, answer),
+                    });
+                }
+            }
+            result.extras = answers.extras;
+        }
+        Err(e) => result.error = Some(format!("{:#}", e)),
+    }
+}
+
+pub(crate) fn solve_input(day: &Day, input: &str, ctx: &Context) -> Result<Answers> {
+    // A panicking solver should be reported like any other failure rather
+    // than abort the rest of the run.
+    util::catch_panic(|| (day.solve)(input, ctx))
+        .unwrap_or_else(|message| Err(anyhow!("solver panicked: {}", message)))
+}
+
+fn print_result(result: &DayResult) {
+    println!(
+        "{} day {:02}  {:<10} {:>10}",
+        result.year,
+        result.day,
+        result.status(),
+        format!("{:.2?}", result.elapsed)
+    );
+    for part in result.parts.iter() {
+        println!("  part {}: {} ({})", part.part, part.answer, part.verdict);
+    }
+    for extra in result.extras.iter() {
+        println!("  {}", extra);
+    }
+    for line in result.trace.iter() {
+        println!("  part {} | {}", line.part, line.message);
+    }
+    if let Some(error) = &result.error {
+        println!("  error: {}", error);
+    }
+}
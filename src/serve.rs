@@ -0,0 +1,485 @@
+//! A small local HTTP API for solving posted inputs.
+//!
+//! `POST /<year>/day/<day>` with the puzzle input as the body answers with
+//! the day's solution and how long it took:
+//!
+//! ```text
+//! $ curl --data-binary @inputs/2023/day09.txt localhost:8023/2023/day/9
+//! {"answers":{"part_1":"114","part_2":"2"},"day":9,"timings":{"parse":0.00005,"solve":0.00004},"year":2023}
+//! ```
+//!
+//! Failures are reported as `{"error": "..."}` with a matching status code.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context as _, Result};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    config::Config,
+    days::{self, Answers, Context, Day},
+    util,
+};
+
+/// Longest request line or header the server reads.
+const MAX_LINE: u64 = 8 * 1024;
+const MAX_HEADERS: usize = 100;
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Most of a rejected request body that is read and thrown away.
+const MAX_DRAIN: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Largest accepted request body, in bytes.
+    pub max_body: usize,
+    /// How long a request may spend solving before it is abandoned.
+    pub time_budget: Duration,
+    /// Most solves running at once, abandoned ones included.
+    pub max_solves: usize,
+}
+
+/// Counts the solver threads still running, so abandoned solves can't pile
+/// up without bound.
+#[derive(Debug, Clone)]
+struct Slots {
+    running: Arc<AtomicUsize>,
+    capacity: usize,
+}
+
+/// A claim on one of the slots, given back when dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slots {
+    fn new(capacity: usize) -> Self {
+        Slots {
+            running: Arc::new(AtomicUsize::new(0)),
+            capacity,
+        }
+    }
+
+    fn claim(&self) -> Option<Slot> {
+        self.running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |running| {
+                (running < self.capacity).then_some(running + 1)
+            })
+            .ok()
+            .map(|_| Slot(Arc::clone(&self.running)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Solution {
+    year: u16,
+    day: u8,
+    answers: Answers,
+    timings: Timings,
+}
+
+#[derive(Debug, Serialize)]
+struct Timings {
+    #[serde(serialize_with = "util::as_secs")]
+    parse: Duration,
+    #[serde(serialize_with = "util::as_secs")]
+    solve: Duration,
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: String,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl ToString) -> Self {
+        Response {
+            status,
+            body: json!({ "error": message.to_string() }),
+        }
+    }
+
+    fn write_to(&self, mut stream: &TcpStream) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            body.len(),
+            body
+        )?;
+        stream.flush()
+    }
+}
+
+/// Serves the API on `addr` until the process is stopped.
+pub fn run(config: &Config, addr: &str, limits: Limits) -> Result<()> {
+    let listener = TcpListener::bind(addr).with_context(|| format!("binding {}", addr))?;
+    println!("listening on http://{}", listener.local_addr()?);
+    serve(listener, config, limits);
+    Ok(())
+}
+
+fn serve(listener: TcpListener, config: &Config, limits: Limits) {
+    let slots = Slots::new(limits.max_solves);
+    thread::scope(|scope| {
+        for stream in listener.incoming().flatten() {
+            let slots = slots.clone();
+            scope.spawn(move || {
+                // the client has gone away, there is nobody left to tell
+                _ = handle(stream, config, limits, &slots);
+            });
+        }
+    });
+}
+
+fn handle(stream: TcpStream, config: &Config, limits: Limits, slots: &Slots) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let response = match read_request(&mut BufReader::new(&stream), limits.max_body) {
+        Ok(request) => respond(request, config, limits, slots),
+        Err(response) => response,
+    };
+    response.write_to(&stream)?;
+    // Closing with an unread body would reset the connection, and the client
+    // might never see the response.
+    stream.shutdown(Shutdown::Write)?;
+    io::copy(&mut (&stream).take(MAX_DRAIN), &mut io::sink())?;
+    Ok(())
+}
+
+fn read_request(reader: &mut impl BufRead, max_body: usize) -> Result<Request, Response> {
+    let mut read_line = || -> Result<String, Response> {
+        let mut line = String::new();
+        reader
+            .take(MAX_LINE)
+            .read_line(&mut line)
+            .map_err(|e| Response::error(400, e))?;
+        if !line.ends_with('\n') {
+            return Err(Response::error(431, "request line or header too long"));
+        }
+        Ok(line.trim_end().to_owned())
+    };
+
+    let request_line = read_line()?;
+    let mut parts = request_line.split_ascii_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let (method, path) = (method.to_owned(), path.to_owned());
+
+    let mut content_length = None;
+    for _ in 0..MAX_HEADERS {
+        let header = read_line()?;
+        if header.is_empty() {
+            return read_body(reader, content_length, max_body).map(|body| Request {
+                method,
+                path,
+                body,
+            });
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let length = value.trim().parse::<usize>();
+                content_length =
+                    Some(length.map_err(|_| Response::error(400, "bad content length"))?);
+            }
+        }
+    }
+    Err(Response::error(431, "too many headers"))
+}
+
+fn read_body(
+    reader: &mut impl BufRead,
+    content_length: Option<usize>,
+    max_body: usize,
+) -> Result<String, Response> {
+    let length = content_length.unwrap_or(0);
+    if length > max_body {
+        return Err(Response::error(
+            413,
+            format!("input is {} bytes, the limit is {}", length, max_body),
+        ));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| Response::error(400, e))?;
+    String::from_utf8(body).map_err(|_| Response::error(400, "input is not UTF-8"))
+}
+
+fn respond(request: Request, config: &Config, limits: Limits, slots: &Slots) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let [year, "day", day] = segments[..] else {
+        return Response::error(404, format!("no route for {}", request.path));
+    };
+    let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+        return Response::error(404, format!("no route for {}", request.path));
+    };
+    if request.method != "POST" {
+        return Response::error(405, "post the puzzle input to this path");
+    }
+    let Some(day) = days::find(year, day) else {
+        return Response::error(
+            404,
+            format!("no solution registered for {} day {}", year, day),
+        );
+    };
+
+    let ctx = Context {
+        params: config.params(day.year, day.day),
+        ..Context::default()
+    };
+    match solve_within(day, request.body, ctx, limits.time_budget, slots) {
+        Ok(solution) => Response {
+            status: 200,
+            body: serde_json::to_value(solution).expect("solutions serialize"),
+        },
+        Err(response) => response,
+    }
+}
+
+/// Solves on a separate thread so the request can give up once the time
+/// budget runs out. A solver cannot be stopped from outside, so an abandoned
+/// one carries on in the background until it finishes, holding its slot.
+fn solve_within(
+    day: &'static Day,
+    input: String,
+    ctx: Context,
+    budget: Duration,
+    slots: &Slots,
+) -> Result<Solution, Response> {
+    let Some(slot) = slots.claim() else {
+        return Err(Response::error(
+            503,
+            format!("already running {} solves, try again later", slots.capacity),
+        ));
+    };
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        let start = Instant::now();
+        match util::catch_panic(|| (day.parse)(&input)) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                _ = sender.send(Err(Response::error(400, format!("{:#}", e))));
+                return;
+            }
+            Err(message) => {
+                let message = format!("parser panicked: {}", message);
+                _ = sender.send(Err(Response::error(500, message)));
+                return;
+            }
+        }
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let result = match util::catch_panic(|| (day.solve)(&input, &ctx)) {
+            Ok(Ok(answers)) => Ok(Solution {
+                year: day.year,
+                day: day.day,
+                answers,
+                timings: Timings {
+                    parse,
+                    solve: start.elapsed(),
+                },
+            }),
+            Ok(Err(e)) => Err(Response::error(422, format!("{:#}", e))),
+            Err(message) => Err(Response::error(
+                500,
+                format!("solver panicked: {}", message),
+            )),
+        };
+        _ = sender.send(result);
+    });
+
+    receiver.recv_timeout(budget).unwrap_or_else(|_| {
+        Err(Response::error(
+            503,
+            format!("no answer within the time budget of {:?}", budget),
+        ))
+    })
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::SocketAddr;
+
+    const LIMITS: Limits = Limits {
+        max_body: 1024,
+        time_budget: Duration::from_millis(500),
+        max_solves: 4,
+    };
+
+    fn start_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener, &Config::default(), LIMITS));
+        addr
+    }
+
+    /// Sends a request and returns the response status and JSON body.
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, serde_json::Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head
+            .split_ascii_whitespace()
+            .nth(1)
+            .unwrap()
+            .parse()
+            .unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_serve() {
+        let addr = start_server();
+
+        let (status, body) = request(
+            addr,
+            "POST",
+            "/2023/day/9",
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["answers"], json!({ "part_1": "114", "part_2": "2" }));
+        assert!(body["timings"]["solve"].is_f64());
+
+        assert_eq!(request(addr, "POST", "/2023/day/9", "1 x 3\n").0, 400);
+        assert_eq!(request(addr, "GET", "/2023/day/9", "").0, 405);
+        assert_eq!(request(addr, "POST", "/2023/day/26", "").0, 404);
+        assert_eq!(request(addr, "POST", "/nowhere", "").0, 404);
+
+        let (status, body) = request(addr, "POST", "/2023/day/9", &"1 ".repeat(1000));
+        assert_eq!(status, 413);
+        assert!(body["error"].as_str().unwrap().contains("limit"));
+    }
+
+    fn slow_solve(_: &str, _: &Context) -> Result<Answers> {
+        thread::sleep(Duration::from_secs(2));
+        Ok(Answers::default())
+    }
+
+    static SLOW: Day = Day::new(2023, 0, slow_solve, |_| Ok(()), |_, _| String::new());
+
+    #[test]
+    fn test_serve_time_budget() {
+        let slots = Slots::new(LIMITS.max_solves);
+        let response = solve_within(
+            &SLOW,
+            String::new(),
+            Context::default(),
+            LIMITS.time_budget,
+            &slots,
+        )
+        .unwrap_err();
+        assert_eq!(response.status, 503);
+        assert!(response.body["error"]
+            .as_str()
+            .unwrap()
+            .contains("time budget"));
+
+        // a day that can never finish is a client error, not a timeout
+        let addr = start_server();
+        let input = "L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(request(addr, "POST", "/2023/day/8", input).0, 422);
+    }
+
+    #[test]
+    fn test_serve_max_solves() {
+        let slots = Slots::new(1);
+        let solve = || {
+            solve_within(
+                &SLOW,
+                String::new(),
+                Context::default(),
+                Duration::from_millis(50),
+                &slots,
+            )
+            .unwrap_err()
+        };
+        let error = |response: Response| response.body["error"].as_str().unwrap().to_owned();
+        assert!(error(solve()).contains("time budget"));
+        // the abandoned solve still holds the only slot
+        assert!(error(solve()).contains("already running"));
+    }
+
+    static PANICKY: Day = Day::new(
+        2023,
+        0,
+        |_, _| panic!("solver gave up"),
+        |input| {
+            assert!(!input.is_empty(), "parser gave up");
+            Ok(())
+        },
+        |_, _| String::new(),
+    );
+
+    #[test]
+    fn test_serve_panics() {
+        let slots = Slots::new(LIMITS.max_solves);
+        for (input, message) in [("", "parser gave up"), ("x", "solver gave up")] {
+            let response = solve_within(
+                &PANICKY,
+                input.to_string(),
+                Context::default(),
+                LIMITS.time_budget,
+                &slots,
+            )
+            .unwrap_err();
+            assert_eq!(response.status, 500);
+            assert!(response.body["error"].as_str().unwrap().contains(message));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+
+use crate::{
+    config::{Config, Format},
+    days::{Context, Day},
+    runner, util,
+};
+
+/// One timed solve of a generated input.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Sample {
+    pub size: usize,
+    pub bytes: usize,
+    #[serde(serialize_with = "util::as_secs")]
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayStress {
+    pub year: u16,
+    pub day: u8,
+    pub samples: Vec<Sample>,
+    /// Fitted exponent `k` of the growth `time ~ size^k`.
+    pub growth: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Generates an input for `day`, reproducible from `seed` and `size`.
+pub fn generate(day: &Day, size: usize, seed: u64) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (day.generate)(&mut rng, size)
+}
+
+/// Times each selected day on generated inputs of increasing size and
+/// reports how the running time grows.
+pub fn run(
+    config: &Config,
+    year: Option<u16>,
+    day: Option<u8>,
+    sizes: &[usize],
+    seed: u64,
+) -> Result<()> {
+    let selected = runner::select(year, day)?;
+
+    let mut reports = Vec::new();
+    for day in selected {
+        let report = stress_day(config, day, sizes, seed);
+        match config.format {
+            Format::Text => print_report(&report),
+            Format::Json => reports.push(report),
+        }
+    }
+    if config.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(())
+}
+
+fn stress_day(config: &Config, day: &Day, sizes: &[usize], seed: u64) -> DayStress {
+    let ctx = Context {
+        params: config.params(day.year, day.day),
+        ..Context::default()
+    };
+    let mut report = DayStress {
+        year: day.year,
+        day: day.day,
+        samples: Vec::new(),
+        growth: None,
+        error: None,
+    };
+    for &size in sizes {
+        let input = generate(day, size, seed);
+        let start = Instant::now();
+        if let Err(e) = runner::solve_input(day, &input, &ctx) {
+            // larger inputs are unlikely to fare any better
+            report.error = Some(format!("size {}: {:#}", size, e));
+            break;
+        }
+        report.samples.push(Sample {
+            size,
+            bytes: input.len(),
+            elapsed: start.elapsed(),
+        });
+    }
+    report.growth = growth_exponent(&report.samples);
+    report
+}
+
+/// Least squares slope of log(time) against log(size).
+fn growth_exponent(samples: &[Sample]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.size > 0 && !s.elapsed.is_zero())
+        .map(|s| ((s.size as f64).ln(), s.elapsed.as_secs_f64().ln()))
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+fn print_report(report: &DayStress) {
+    println!("{} Day {:02}", report.year, report.day);
+    println!(
+        "{:>10} {:>12} {:>12} {:>8}",
+        "size", "bytes", "time", "growth"
+    );
+    let mut previous: Option<&Sample> = None;
+    for sample in report.samples.iter() {
+        let step = previous
+            .and_then(|p| growth_exponent(&[*p, *sample]))
+            .map(|k| format!("{:.2}", k))
+            .unwrap_or_default();
+        println!(
+            "{:>10} {:>12} {:>12} {:>8}",
+            sample.size,
+            sample.bytes,
+            format!("{:.2?}", sample.elapsed),
+            step
+        );
+        previous = Some(sample);
+    }
+    if let Some(k) = report.growth {
+        println!("overall: time ~ size^{:.2}", k);
+    }
+    if let Some(error) = &report.error {
+        println!("error: {}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_growth_exponent() {
+        let samples: Vec<Sample> = [10, 100, 1000]
+            .into_iter()
+            .map(|size| Sample {
+                size,
+                bytes: 0,
+                elapsed: Duration::from_nanos((size * size) as u64),
+            })
+            .collect();
+        assert!((growth_exponent(&samples).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(growth_exponent(&samples[..1]), None);
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in days::all() {
+            for size in [1, 10, 50] {
+                let input = generate(day, size, 7);
+                assert_eq!(input, generate(day, size, 7));
+                if let Err(e) = runner::solve_input(day, &input, &Context::default()) {
+                    panic!(
+                        "{} day {} size {}: {:#}\n{}",
+                        day.year, day.day, size, e, input
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! An interactive terminal dashboard listing every selected day with its
+//! status, answers and timing.
+
+use std::{collections::VecDeque, io, time::Duration};
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::{
+    answers::Manifest,
+    config::Config,
+    days::{Day, Trace},
+    runner::{self, DayResult, Status},
+    viz::Frames,
+};
+
+const HELP: &str =
+    "↑/↓ select · r run · a run all · s switch input set · 1/2 part trace · esc close · q quit";
+
+struct App {
+    days: Vec<&'static Day>,
+    /// The default inputs followed by every named input set.
+    sets: Vec<(String, Config)>,
+    set: usize,
+    /// The latest result for each day in the current set.
+    results: Vec<Option<DayResult>>,
+    /// Days waiting to be run, solved one per frame so the table keeps up.
+    pending: VecDeque<usize>,
+    table: TableState,
+    /// The part whose trace is open.
+    trace: Option<u8>,
+    quit: bool,
+}
+
+/// Runs the dashboard until the user quits.
+pub fn run(config: &Config, year: Option<u16>, day: Option<u8>) -> Result<()> {
+    let mut app = App::new(config, runner::select(year, day)?)?;
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let result = Terminal::new(CrosstermBackend::new(io::stdout()))
+        .map_err(anyhow::Error::from)
+        .and_then(|mut terminal| app.event_loop(&mut terminal));
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    result
+}
+
+impl App {
+    fn new(config: &Config, days: Vec<&'static Day>) -> Result<Self> {
+        let mut sets = vec![(String::from("default"), config.clone())];
+        for name in config.input_sets()? {
+            sets.push((name.clone(), config.with_set(&name)));
+        }
+        Ok(App {
+            results: days.iter().map(|_| None).collect(),
+            days,
+            sets,
+            set: 0,
+            pending: VecDeque::new(),
+            table: TableState::default().with_selected(Some(0)),
+            trace: None,
+            quit: false,
+        })
+    }
+
+    fn event_loop(&mut self, terminal: &mut Terminal<impl Backend>) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            // keep solving between frames, but stay responsive to keys
+            let timeout = if self.pending.is_empty() {
+                Duration::from_millis(250)
+            } else {
+                Duration::ZERO
+            };
+            if event::poll(timeout)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_key(key.code);
+                    }
+                }
+            } else if let Some(index) = self.pending.pop_front() {
+                self.run_day(index);
+            }
+        }
+        Ok(())
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        let last = self.days.len().saturating_sub(1);
+        match key {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.table.select(Some(self.selected().saturating_sub(1)))
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.table.select(Some((self.selected() + 1).min(last)))
+            }
+            KeyCode::Char('r') | KeyCode::Enter => self.pending.push_back(self.selected()),
+            KeyCode::Char('a') => self.pending = (0..self.days.len()).collect(),
+            KeyCode::Char('s') => {
+                // results belong to the set they were run against
+                self.set = (self.set + 1) % self.sets.len();
+                self.results.iter_mut().for_each(|result| *result = None);
+                self.pending.clear();
+            }
+            KeyCode::Char(c @ ('1' | '2')) => self.trace = Some(c as u8 - b'0'),
+            KeyCode::Esc => self.trace = None,
+            _ => (),
+        }
+    }
+
+    /// Runs a day, recording a broken answer manifest as its error rather
+    /// than closing the dashboard.
+    fn run_day(&mut self, index: usize) {
+        let day = self.days[index];
+        let config = &self.sets[self.set].1;
+        let result = match Manifest::load(config.answers_path(day.year)) {
+            Ok(manifest) => {
+                runner::run_day(config, day, &manifest, Trace::enabled(), Frames::default())
+            }
+            Err(e) => {
+                let mut result = DayResult::new(day);
+                result.error = Some(format!("{:#}", e));
+                result
+            }
+        };
+        self.results[index] = Some(result);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let mut constraints = vec![Constraint::Min(3), Constraint::Length(1)];
+        if self.trace.is_some() {
+            constraints.insert(1, Constraint::Percentage(50));
+        }
+        let areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(constraints)
+            .split(frame.size());
+
+        self.draw_table(frame, areas[0]);
+        if let Some(part) = self.trace {
+            self.draw_trace(frame, areas[1], part);
+        }
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+            areas[areas.len() - 1],
+        );
+    }
+
+    fn draw_table(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.days.iter().enumerate().map(|(index, day)| {
+            let (status, style) = if self.pending.contains(&index) {
+                (String::from("running"), Style::default().fg(Color::Cyan))
+            } else {
+                match &self.results[index] {
+                    None => (
+                        String::from("unsolved"),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Some(result) => (result.status().to_string(), status_style(result.status())),
+                }
+            };
+            let mut cells = vec![
+                format!("{} day {:02}", day.year, day.day),
+                status,
+                String::new(),
+                String::new(),
+                String::new(),
+            ];
+            if let Some(result) = &self.results[index] {
+                for part in result.parts.iter() {
+                    cells[part.part as usize + 1] = part.answer.clone();
+                }
+                if let Some(error) = &result.error {
+                    cells[2] = error.clone();
+                }
+                cells[4] = format!("{:.2?}", result.elapsed);
+            }
+            Row::new(cells).style(style)
+        });
+
+        let widths = [
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Percentage(40),
+            Constraint::Percentage(40),
+            Constraint::Length(12),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(["day", "status", "part 1", "part 2", "time"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" input set: {} ", self.sets[self.set].0)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn draw_trace(&self, frame: &mut Frame, area: Rect, part: u8) {
+        let day = self.days[self.selected()];
+        let lines: Vec<Line> = match &self.results[self.selected()] {
+            None => vec![Line::from("not run yet")],
+            Some(result) => {
+                let lines: Vec<Line> = result
+                    .trace
+                    .iter()
+                    .filter(|line| line.part == part)
+                    .map(|line| Line::from(line.message.clone()))
+                    .collect();
+                if lines.is_empty() {
+                    vec![Line::from("nothing traced")]
+                } else {
+                    lines
+                }
+            }
+        };
+        let title = format!(" {} day {:02} part {} trace ", day.year, day.day, part);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            area,
+        );
+    }
+}
+
+fn status_style(status: Status) -> Style {
+    let color = match status {
+        Status::Correct => Color::Green,
+        Status::Unverified => Color::Yellow,
+        Status::Wrong => Color::Red,
+        Status::Error => Color::Magenta,
+    };
+    Style::default().fg(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    use ratatui::backend::TestBackend;
+    use std::{fs, path::PathBuf};
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(100)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>() + "\n")
+            .collect()
+    }
+
+    #[test]
+    fn test_dashboard() {
+        let dir = std::env::temp_dir().join(format!("advent-tui-{}", std::process::id()));
+        let write = |path: PathBuf, content: &str| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        let config = Config::parse(&format!(
+            "input_dir = {:?}\nanswers_dir = {:?}\n",
+            dir.join("inputs"),
+            dir.join("answers")
+        ))
+        .unwrap();
+        write(
+            config.input_path(2023, 9),
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n",
+        );
+        write(config.answers_path(2023), "09 1 114\n09 2 3\n");
+        write(config.with_set("alice").input_path(2023, 9), "1 2 3\n");
+
+        let mut app = App::new(&config, days::select(Some(2023), None)).unwrap();
+        assert!(screen(&mut app).contains("unsolved"));
+
+        let day09 = app.days.iter().position(|day| day.day == 9).unwrap();
+        app.table.select(Some(day09));
+        app.handle_key(KeyCode::Char('r'));
+        assert!(screen(&mut app).contains("running"));
+        let index = app.pending.pop_front().unwrap();
+        app.run_day(index);
+        let shown = screen(&mut app);
+        assert!(shown.contains("wrong"), "{}", shown);
+        assert!(shown.contains("114"), "{}", shown);
+
+        app.handle_key(KeyCode::Char('1'));
+        assert!(screen(&mut app).contains("part 1 trace"));
+
+        app.handle_key(KeyCode::Char('s'));
+        let shown = screen(&mut app);
+        assert!(shown.contains("input set: alice"), "{}", shown);
+        assert!(!shown.contains("114"), "{}", shown);
+
+        // a broken manifest is that day's error, not the dashboard's
+        write(config.answers_path(2023), "not an answer\n");
+        app.handle_key(KeyCode::Char('s'));
+        app.run_day(day09);
+        let shown = screen(&mut app);
+        assert!(shown.contains("error"), "{}", shown);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
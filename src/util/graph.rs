@@ -0,0 +1,346 @@
+//! Searches over graphs that days describe by their neighbours, rather than
+//! building an adjacency list up front.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one edge away from `node`.
+    fn neighbours(&self, node: &Self::Node) -> impl Iterator<Item = Self::Node>;
+}
+
+/// A graph whose edges cost something to cross.
+pub trait Weighted: Graph {
+    /// The nodes one edge away from `node`, with what the edge costs.
+    fn edges(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+}
+
+impl<N: Clone + Eq + Hash> Graph for HashMap<N, Vec<N>> {
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> impl Iterator<Item = N> {
+        self.get(node).into_iter().flatten().cloned()
+    }
+}
+
+/// How many edges each node reachable from `start` is from it.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node] + 1;
+        for next in graph.neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// The nodes reachable from `start` in depth first order, each following
+/// the first of its neighbours not yet seen.
+pub fn dfs<G: Graph>(graph: &G, start: G::Node) -> Vec<G::Node> {
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<G::Node> = graph
+            .neighbours(&node)
+            .filter(|next| !seen.contains(next))
+            .collect();
+        // the first neighbour goes on top so it is explored first
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+    order
+}
+
+/// The cheapest path from `start` to a node satisfying `is_goal`, with its
+/// cost, or `None` if no goal can be reached.
+pub fn dijkstra<G: Weighted>(
+    graph: &G,
+    start: G::Node,
+    is_goal: impl FnMut(&G::Node) -> bool,
+) -> Option<(u64, Vec<G::Node>)> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Like [`dijkstra`], but exploring first the nodes `heuristic` estimates
+/// are closest to a goal. The estimate must never exceed the true remaining
+/// cost for the path found to be the cheapest.
+pub fn astar<G: Weighted>(
+    graph: &G,
+    start: G::Node,
+    mut is_goal: impl FnMut(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> u64,
+) -> Option<(u64, Vec<G::Node>)> {
+    // The heap holds indices into `nodes` so nodes need no ordering, with
+    // stale entries skipped once a cheaper way to their node is known.
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+    let mut costs = HashMap::from([(start, 0)]);
+    let mut parents: HashMap<G::Node, G::Node> = HashMap::new();
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if costs[&node] < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(parent) = parents.get(&path[path.len() - 1]) {
+                path.push(parent.clone());
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        for (next, edge) in graph.edges(&node) {
+            let next_cost = cost + edge;
+            if costs.get(&next).is_none_or(|&known| next_cost < known) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    nodes.len(),
+                )));
+                nodes.push(next);
+            }
+        }
+    }
+    None
+}
+
+/// Where the sequence `start, f(start), f(f(start)), ...` starts repeating,
+/// by Brent's algorithm: the index of the first value in the cycle and the
+/// cycle's length. The sequence must eventually repeat, as any does over
+/// finitely many states.
+pub fn brent<T: Clone + PartialEq>(start: T, mut f: impl FnMut(&T) -> T) -> (usize, usize) {
+    // the hare runs ahead in doubling stretches until the tortoise, waiting
+    // at the start of each, sees it come round
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // with the hare a cycle ahead, they meet where the cycle starts
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        offset += 1;
+    }
+    (offset, length)
+}
+
+/// The strongly connected components of the graph reachable from `roots`,
+/// by Tarjan's algorithm. Components come out in reverse topological order,
+/// so none has an edge into a later one.
+pub fn strongly_connected<G: Graph>(
+    graph: &G,
+    roots: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    struct Visit {
+        index: usize,
+        low: usize,
+        on_stack: bool,
+    }
+
+    let mut visits: HashMap<G::Node, Visit> = HashMap::new();
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    for root in roots {
+        if visits.contains_key(&root) {
+            continue;
+        }
+        // each frame is a node and the neighbours it has still to look at,
+        // standing in for the recursion of the usual formulation
+        let mut frames: Vec<(G::Node, Vec<G::Node>)> = Vec::new();
+        let enter =
+            |node: G::Node, visits: &mut HashMap<G::Node, Visit>, stack: &mut Vec<G::Node>| {
+                let index = visits.len();
+                visits.insert(
+                    node.clone(),
+                    Visit {
+                        index,
+                        low: index,
+                        on_stack: true,
+                    },
+                );
+                stack.push(node.clone());
+                let mut neighbours: Vec<G::Node> = graph.neighbours(&node).collect();
+                neighbours.reverse();
+                (node, neighbours)
+            };
+        frames.push(enter(root, &mut visits, &mut stack));
+
+        while let Some((node, neighbours)) = frames.last_mut() {
+            if let Some(next) = neighbours.pop() {
+                match visits.get(&next) {
+                    None => {
+                        let frame = enter(next, &mut visits, &mut stack);
+                        frames.push(frame);
+                    }
+                    Some(visit) if visit.on_stack => {
+                        let index = visit.index;
+                        let current = visits.get_mut(node).unwrap();
+                        current.low = current.low.min(index);
+                    }
+                    Some(_) => (),
+                }
+                continue;
+            }
+
+            let node = node.clone();
+            frames.pop();
+            let visit = &visits[&node];
+            let (index, low) = (visit.index, visit.low);
+            if let Some((parent, _)) = frames.last() {
+                let parent = visits.get_mut(parent).unwrap();
+                parent.low = parent.low.min(low);
+            }
+            if low == index {
+                let mut component = Vec::new();
+                loop {
+                    let member = stack.pop().unwrap();
+                    visits.get_mut(&member).unwrap().on_stack = false;
+                    let done = member == node;
+                    component.push(member);
+                    if done {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Open cells of a maze, where stepping onto a digit costs that much
+    /// and anything else costs one.
+    struct Maze(Vec<Vec<u8>>);
+
+    impl Graph for Maze {
+        type Node = (usize, usize);
+
+        fn neighbours(&self, &(row, col): &(usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+            [(0, 1), (2, 1), (1, 0), (1, 2)]
+                .into_iter()
+                .filter_map(move |(dr, dc)| {
+                    let (row, col) = ((row + dr).checked_sub(1)?, (col + dc).checked_sub(1)?);
+                    let cell = *self.0.get(row)?.get(col)?;
+                    (cell != b'#').then_some((row, col))
+                })
+        }
+    }
+
+    impl Weighted for Maze {
+        fn edges(&self, node: &(usize, usize)) -> impl Iterator<Item = ((usize, usize), u64)> {
+            self.neighbours(node).map(|(row, col)| {
+                let cell = self.0[row][col];
+                let cost = if cell.is_ascii_digit() {
+                    (cell - b'0') as u64
+                } else {
+                    1
+                };
+                ((row, col), cost)
+            })
+        }
+    }
+
+    fn maze() -> Maze {
+        Maze(
+            ["....#", ".##.#", ".9...", "..#.#", "#...."]
+                .iter()
+                .map(|row| row.bytes().collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let maze = maze();
+        let distances = bfs(&maze, (0, 0));
+        assert_eq!(distances[&(4, 4)], 8);
+        assert_eq!(distances.len(), 18);
+        assert!(!distances.contains_key(&(0, 4)));
+
+        let order = dfs(&maze, (0, 0));
+        assert_eq!(order.len(), 18);
+        // down is tried before right
+        assert_eq!(order[..3], [(0, 0), (1, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let maze = maze();
+        let goal = |node: &(usize, usize)| *node == (4, 4);
+        let (cost, path) = dijkstra(&maze, (0, 0), goal).unwrap();
+        // around the costly 9 rather than through it
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert!(!path.contains(&(2, 1)));
+
+        let manhattan = |&(row, col): &(usize, usize)| (4 - row + 4 - col) as u64;
+        let (cost, astar_path) = astar(&maze, (0, 0), goal, manhattan).unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(astar_path.len(), 9);
+
+        assert_eq!(dijkstra(&maze, (0, 0), |node| *node == (0, 4)), None);
+    }
+
+    #[test]
+    fn test_brent() {
+        // 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, ...
+        assert_eq!(brent(0, |&x| if x == 5 { 2 } else { x + 1 }), (2, 4));
+        assert_eq!(brent(7, |&x| x), (0, 1));
+        assert_eq!(brent(1u64, |&x| x * 3 % 1000), (0, 100));
+    }
+
+    #[test]
+    fn test_strongly_connected() {
+        let graph: HashMap<u8, Vec<u8>> = HashMap::from([
+            (1, vec![2]),
+            (2, vec![3, 4]),
+            (3, vec![1]),
+            (4, vec![5]),
+            (5, vec![4, 6]),
+            (6, vec![]),
+        ]);
+        let mut components = strongly_connected(&graph, [1, 6]);
+        for component in components.iter_mut() {
+            component.sort_unstable();
+        }
+        assert_eq!(components, vec![vec![6], vec![4, 5], vec![1, 2, 3]]);
+    }
+}
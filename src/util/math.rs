@@ -0,0 +1,294 @@
+//! Number theory and geometry shared between days.
+
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, bail, Result};
+use num_integer::Integer;
+
+/// Solves the system `x ≡ r (mod m)` for every `(r, m)` pair, where the
+/// moduli need not be coprime. Returns the smallest non-negative solution
+/// and the period it repeats with, the lcm of the moduli, or `None` when the
+/// congruences contradict each other, a modulus is not positive or the
+/// period does not fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut period) = (0i128, 1i128);
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+        let modulus = modulus as i128;
+        let residue = (residue as i128).rem_euclid(modulus);
+
+        // x + period·t ≡ residue (mod modulus) is solvable exactly when the
+        // gcd divides the gap, and then t is fixed modulo modulus / gcd
+        let gcd = period.extended_gcd(&modulus);
+        let gap = residue - x;
+        if gap % gcd.gcd != 0 {
+            return None;
+        }
+        let step = modulus / gcd.gcd;
+        let t = (gap / gcd.gcd % step * (gcd.x % step)).rem_euclid(step);
+        let lcm = period * step;
+        x = (x + period * t).rem_euclid(lcm);
+        period = lcm;
+        if period > i64::MAX as i128 {
+            return None;
+        }
+    }
+    Some((x as i64, period as i64))
+}
+
+/// The largest integer whose square is at most `n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method falls monotonically towards the root from any start
+    // above it
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// The integers `x` at which `a·x² + b·x + c ≤ 0` for `a > 0`, the closed
+/// range between the parabola's real roots clamped to `i64`, or `None` if no
+/// integer lies between them. Fails for `a ≤ 0` or when the working values
+/// outgrow an `i128`.
+pub fn quadratic_bounds(a: i64, b: i64, c: i64) -> Result<Option<RangeInclusive<i64>>> {
+    if a <= 0 {
+        bail!("the parabola must open upwards, but a is {}", a);
+    }
+    let overflow = || anyhow!("{}x² + {}x + {} is too large to bound", a, b, c);
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let f = |x: i128| {
+        a.checked_mul(x)
+            .and_then(|ax| ax.checked_add(b))
+            .and_then(|axb| axb.checked_mul(x))
+            .and_then(|axbx| axbx.checked_add(c))
+            .ok_or_else(overflow)
+    };
+    let discriminant = (b * b)
+        .checked_sub(
+            4i128
+                .checked_mul(a)
+                .and_then(|a4| a4.checked_mul(c))
+                .ok_or_else(overflow)?,
+        )
+        .ok_or_else(overflow)?;
+    if discriminant < 0 {
+        return Ok(None);
+    }
+
+    // The true square root lies in [root, root + 1), which puts each real
+    // root within half a step of the first guess.
+    let root = isqrt(discriminant as u128) as i128;
+    let mut low = -(b + root + 1).div_euclid(2 * a);
+    if f(low)? > 0 {
+        low += 1;
+    }
+    let mut high = (root + 1 - b).div_euclid(2 * a);
+    if f(high)? > 0 {
+        high -= 1;
+    }
+    let low = low.max(i64::MIN as i128) as i64;
+    let high = high.min(i64::MAX as i128) as i64;
+    Ok((low <= high).then_some(low..=high))
+}
+
+/// The value following `values`, taken as consecutive values of a
+/// polynomial of lower degree than there are values. Each row of finite
+/// differences is one degree lower, until one is all zeros, and the next
+/// value is the sum of the rows' last entries. Fails if a difference or the
+/// next value doesn't fit.
+pub fn extrapolate(values: &[i64]) -> Result<i64> {
+    let overflow = || anyhow!("extrapolating {:?} overflows", values);
+    // differences can outgrow the values themselves
+    let mut row: Vec<i128> = values.iter().map(|&value| value as i128).collect();
+    let mut next = 0i128;
+    while row.iter().any(|&value| value != 0) {
+        next = next.checked_add(row[row.len() - 1]).ok_or_else(overflow)?;
+        row = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()
+            .ok_or_else(overflow)?;
+    }
+    i64::try_from(next).map_err(|_| overflow())
+}
+
+/// Twice the signed area of the polygon through `vertices` in order,
+/// positive when they turn anticlockwise. Doubling keeps the area of any
+/// lattice polygon whole. Fails if the area doesn't fit.
+pub fn shoelace(vertices: &[(i64, i64)]) -> Result<i64> {
+    i64::try_from(doubled_area(vertices)?).map_err(|_| anyhow!("polygon is too large"))
+}
+
+fn doubled_area(vertices: &[(i64, i64)]) -> Result<i128> {
+    edges(vertices)
+        .map(|(a, b)| a.0 as i128 * b.1 as i128 - b.0 as i128 * a.1 as i128)
+        .try_fold(0i128, |area, term| area.checked_add(term))
+        .ok_or_else(|| anyhow!("polygon is too large"))
+}
+
+/// How many lattice points lie strictly inside the lattice polygon through
+/// `vertices`, by Pick's theorem: the area is `I + B/2 - 1` for `I` points
+/// inside and `B` on the boundary. Fails if the count doesn't fit.
+pub fn interior_points(vertices: &[(i64, i64)]) -> Result<i64> {
+    if vertices.is_empty() {
+        return Ok(0);
+    }
+    let too_large = || anyhow!("polygon is too large");
+    let boundary = edges(vertices)
+        .map(|(a, b)| {
+            (b.0 as i128 - a.0 as i128)
+                .abs()
+                .gcd(&(b.1 as i128 - a.1 as i128).abs())
+        })
+        .try_fold(0i128, |boundary, points| boundary.checked_add(points))
+        .ok_or_else(too_large)?;
+    let interior = doubled_area(vertices)?
+        .checked_abs()
+        .and_then(|area| area.checked_sub(boundary))
+        .ok_or_else(too_large)?
+        / 2
+        + 1;
+    i64::try_from(interior).map_err(|_| too_large())
+}
+
+/// Each vertex paired with the next, the last with the first.
+fn edges(vertices: &[(i64, i64)]) -> impl Iterator<Item = (&(i64, i64), &(i64, i64))> {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // 4 and 6 share a factor, and 3 mod 4 agrees with 5 mod 6 mod 2
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(-1, 10)]), Some((9, 10)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 0)]), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u128 {
+            let root = isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "{}", n);
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn test_quadratic_bounds_extremes() {
+        let root = 3_037_000_499;
+        assert_eq!(
+            quadratic_bounds(1, 0, i64::MIN).unwrap(),
+            Some(-root..=root)
+        );
+        assert_eq!(quadratic_bounds(1, 0, i64::MAX).unwrap(), None);
+        assert_eq!(
+            quadratic_bounds(1, i64::MIN, 0).unwrap(),
+            Some(0..=i64::MAX)
+        );
+        assert_eq!(
+            quadratic_bounds(1, i64::MAX, 0).unwrap(),
+            Some(-i64::MAX..=0)
+        );
+        assert!(quadratic_bounds(i64::MAX, i64::MIN, i64::MIN).is_err());
+        assert!(quadratic_bounds(0, 1, 1).is_err());
+        assert!(quadratic_bounds(i64::MIN, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_interior_points() {
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(shoelace(&square).unwrap(), 32);
+        assert_eq!(interior_points(&square).unwrap(), 9);
+        let clockwise: Vec<(i64, i64)> = square.iter().rev().copied().collect();
+        assert_eq!(shoelace(&clockwise).unwrap(), -32);
+        assert_eq!(interior_points(&clockwise).unwrap(), 9);
+        assert_eq!(interior_points(&[(0, 0), (3, 0), (0, 3)]).unwrap(), 1);
+
+        let huge = [
+            (i64::MIN, i64::MIN),
+            (i64::MAX, i64::MIN),
+            (i64::MAX, i64::MAX),
+        ];
+        assert!(shoelace(&huge).is_err());
+        assert!(interior_points(&huge).is_err());
+    }
+
+    #[test]
+    fn test_extrapolate_extremes() {
+        assert!(extrapolate(&[i64::MAX, i64::MIN]).is_err());
+        // the differences outgrow an i64 but the next value doesn't
+        assert_eq!(
+            extrapolate(&[i64::MIN, i64::MAX, i64::MAX]).unwrap(),
+            i64::MIN
+        );
+        assert_eq!(extrapolate(&[i64::MAX, i64::MAX]).unwrap(), i64::MAX);
+    }
+
+    /// A polynomial's degree and its values at 0, 1, 2, ...
+    fn arb_history() -> impl Strategy<Value = (usize, Vec<i64>)> {
+        prop::collection::vec(-10..=10i64, 1..=5).prop_map(|coefficients| {
+            let values = (0..20)
+                .map(|x: i64| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient)
+                })
+                .collect();
+            (coefficients.len() - 1, values)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_crt_solves_every_congruence(
+            congruences in prop::collection::vec((-50..50i64, 1..12i64), 0..4)
+        ) {
+            let brute = (0..1000)
+                .find(|x| congruences.iter().all(|(r, m)| (x - r).rem_euclid(*m) == 0));
+            prop_assert_eq!(crt(&congruences).map(|(x, _)| x), brute);
+        }
+
+        #[test]
+        fn test_quadratic_bounds_matches_brute_force(
+            a in 1..5i64, b in -100..100i64, c in -500..500i64
+        ) {
+            let inside: Vec<i64> = (-200..=200).filter(|x| a * x * x + b * x + c <= 0).collect();
+            let bounds = quadratic_bounds(a, b, c).unwrap();
+            prop_assert_eq!(bounds.map(|r| r.collect::<Vec<_>>()).unwrap_or_default(), inside);
+        }
+
+        #[test]
+        fn test_extrapolate_reproduces_history((degree, history) in arb_history()) {
+            // degree + 1 values determine the polynomial, so every later
+            // value must be predicted from those before it, and every
+            // earlier one from those after it
+            for k in degree + 1..history.len() {
+                prop_assert_eq!(extrapolate(&history[..k]).unwrap(), history[k]);
+
+                let mut after: Vec<i64> = history[history.len() - k..].to_vec();
+                after.reverse();
+                prop_assert_eq!(extrapolate(&after).unwrap(), history[history.len() - k - 1]);
+            }
+        }
+    }
+}
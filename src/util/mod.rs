@@ -0,0 +1,64 @@
+pub mod graph;
+pub mod math;
+
+use std::{
+    cell::Cell,
+    fs::File,
+    io::{Read, Result},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::Once,
+    time::Duration,
+};
+
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut file_handle = File::open(path)?;
+    let mut content = String::new();
+    file_handle.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Reads a day's input for benchmarks from the configured input directory.
+#[cfg(test)]
+pub fn bench_input(year: u16, day: u8) -> String {
+    let config = crate::config::Config::load(crate::config::DEFAULT_PATH).unwrap();
+    read_input(config.input_path(year, day)).unwrap()
+}
+
+/// Serializes a duration as fractional seconds.
+pub fn as_secs<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, turning a panic into an error carrying its message. The panic
+/// is not printed, but panics elsewhere are reported as usual.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                default_hook(info)
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(was_catching));
+
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
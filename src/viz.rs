@@ -0,0 +1,399 @@
+//! Pictures of the spatial days. A solver records frames of a coloured grid
+//! as it goes, which can then be written out as images, an animation or
+//! straight to the terminal.
+
+use std::{
+    cell::RefCell,
+    fmt::Write as _,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context as _, Result};
+
+use crate::{
+    config::Config,
+    days::{Context, Day},
+    runner, util,
+};
+
+/// Hundredths of a second each animation frame is shown for.
+const GIF_DELAY: u16 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const GREY: Rgb = Rgb(70, 70, 70);
+    pub const WHITE: Rgb = Rgb(230, 230, 230);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(80, 200, 80);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(230, 200, 40);
+
+    /// A colour `t` of the way along a blue to yellow ramp, for showing
+    /// progress through a traversal.
+    pub fn ramp(t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t) as u8;
+        Rgb(
+            mix(Rgb::BLUE.0, Rgb::YELLOW.0),
+            mix(Rgb::BLUE.1, Rgb::YELLOW.1),
+            mix(Rgb::BLUE.2, Rgb::YELLOW.2),
+        )
+    }
+}
+
+/// A rectangle of coloured cells, addressed by row and column like the
+/// puzzle grids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<Rgb>,
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![background; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Colours a cell. Cells outside the grid are ignored.
+    pub fn set(&mut self, row: usize, col: usize, color: Rgb) {
+        if row < self.height && col < self.width {
+            self.cells[row * self.width + col] = color;
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Rgb {
+        self.cells[row * self.width + col]
+    }
+
+    /// RGB bytes of the grid drawn `scale` pixels to a cell, row by row.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.cells.len() * scale * scale * 3);
+        for row in self.cells.chunks(self.width.max(1)) {
+            for _ in 0..scale {
+                for cell in row {
+                    for _ in 0..scale {
+                        bytes.extend([cell.0, cell.1, cell.2]);
+                    }
+                }
+            }
+        }
+        bytes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub part: u8,
+    pub grid: Grid,
+}
+
+/// Frames a solver draws on the way to its answers. Like the trace, nothing
+/// is kept unless someone asked to see them.
+#[derive(Debug, Default)]
+pub struct Frames {
+    enabled: bool,
+    frames: RefCell<Vec<Frame>>,
+}
+
+impl Frames {
+    pub fn enabled() -> Self {
+        Frames {
+            enabled: true,
+            frames: RefCell::default(),
+        }
+    }
+
+    /// Whether frames are being kept, for solvers that need to work out
+    /// something extra only to draw it.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records a frame of `part`. The grid is only drawn when enabled.
+    pub fn record(&self, part: u8, grid: impl FnOnce() -> Grid) {
+        if self.enabled {
+            self.frames.borrow_mut().push(Frame { part, grid: grid() });
+        }
+    }
+
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames.into_inner()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Output {
+    /// One binary PPM image per frame
+    Ppm,
+    /// One PNG image per frame
+    Png,
+    /// An animated GIF per part
+    Gif,
+    /// Coloured text on the terminal
+    Ansi,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub output: Output,
+    /// Images are written to `<dir>/<year>/dayNN/`.
+    pub dir: PathBuf,
+    /// Pixels to a grid cell.
+    pub scale: usize,
+    /// Only this part's frames, or both.
+    pub part: Option<u8>,
+}
+
+/// Solves the selected days from their inputs and writes out whatever
+/// frames they draw. Days without an input are skipped.
+pub fn run(config: &Config, year: Option<u16>, day: Option<u8>, options: &Options) -> Result<()> {
+    let selected = runner::select(year, day)?;
+    // Most days draw nothing and not every input is around, which is only
+    // worth mentioning when one day was asked for.
+    let single = selected.len() == 1;
+    for day in selected {
+        if !single && !config.input_path(day.year, day.day).exists() {
+            continue;
+        }
+        let frames: Vec<Frame> = draw(config, day)?
+            .into_iter()
+            .filter(|frame| options.part.is_none_or(|part| frame.part == part))
+            .collect();
+        if frames.is_empty() {
+            if single {
+                println!("{} day {:02}: nothing to draw", day.year, day.day);
+            }
+            continue;
+        }
+        export(day, &frames, options)?;
+    }
+    Ok(())
+}
+
+/// Solves the day's input again just to collect what it draws.
+pub(crate) fn draw(config: &Config, day: &Day) -> Result<Vec<Frame>> {
+    let path = config.input_path(day.year, day.day);
+    let input = util::read_input(&path).with_context(|| format!("reading {}", path.display()))?;
+    let ctx = Context {
+        params: config.params(day.year, day.day),
+        frames: Frames::enabled(),
+        ..Context::default()
+    };
+    runner::solve_input(day, &input, &ctx)?;
+    Ok(ctx.frames.into_frames())
+}
+
+fn export(day: &Day, frames: &[Frame], options: &Options) -> Result<()> {
+    let dir = options
+        .dir
+        .join(day.year.to_string())
+        .join(format!("day{:02}", day.day));
+    if options.output != Output::Ansi {
+        fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+
+    for part in 1..=2 {
+        let frames: Vec<&Grid> = frames
+            .iter()
+            .filter(|frame| frame.part == part)
+            .map(|frame| &frame.grid)
+            .collect();
+        if frames.is_empty() {
+            continue;
+        }
+        match options.output {
+            Output::Ansi => {
+                for (n, grid) in frames.iter().enumerate() {
+                    println!(
+                        "{} day {:02} part {} frame {}",
+                        day.year,
+                        day.day,
+                        part,
+                        n + 1
+                    );
+                    print!("{}", ansi(grid));
+                }
+            }
+            Output::Gif => {
+                let path = dir.join(format!("part{}.gif", part));
+                write_gif(&frames, options.scale, create(&path)?)
+                    .with_context(|| format!("writing {}", path.display()))?;
+                println!("wrote {}", path.display());
+            }
+            Output::Ppm | Output::Png => {
+                let extension = if options.output == Output::Ppm {
+                    "ppm"
+                } else {
+                    "png"
+                };
+                for (n, grid) in frames.iter().enumerate() {
+                    let path = dir.join(format!("part{}-{:04}.{}", part, n, extension));
+                    let file = create(&path)?;
+                    match options.output {
+                        Output::Ppm => write_ppm(grid, options.scale, file),
+                        _ => write_png(grid, options.scale, file),
+                    }
+                    .with_context(|| format!("writing {}", path.display()))?;
+                }
+                println!(
+                    "wrote {} part {} frames to {}",
+                    frames.len(),
+                    part,
+                    dir.display()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Writes a grid as a binary PPM image.
+pub fn write_ppm(grid: &Grid, scale: usize, mut writer: impl Write) -> Result<()> {
+    write!(
+        writer,
+        "P6\n{} {}\n255\n",
+        grid.width * scale,
+        grid.height * scale
+    )?;
+    writer.write_all(&grid.pixels(scale))?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a grid as a PNG image.
+pub fn write_png(grid: &Grid, scale: usize, writer: impl Write) -> Result<()> {
+    let mut encoder = png::Encoder::new(
+        writer,
+        (grid.width * scale) as u32,
+        (grid.height * scale) as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&grid.pixels(scale))?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Writes frames as a looping GIF animation, sized to fit the largest.
+pub fn write_gif(frames: &[&Grid], scale: usize, writer: impl Write) -> Result<()> {
+    let size = |dimension: usize| {
+        u16::try_from(dimension * scale).map_err(|_| anyhow!("frames are too large for a GIF"))
+    };
+    let width = size(frames.iter().map(|grid| grid.width).max().unwrap_or(0))?;
+    let height = size(frames.iter().map(|grid| grid.height).max().unwrap_or(0))?;
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for grid in frames {
+        let mut frame = gif::Frame::from_rgb_speed(
+            size(grid.width)?,
+            size(grid.height)?,
+            &grid.pixels(scale),
+            10,
+        );
+        frame.delay = GIF_DELAY;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Renders a grid for a truecolor terminal, two rows of cells to a line of
+/// half blocks.
+pub fn ansi(grid: &Grid) -> String {
+    let mut output = String::new();
+    for top in (0..grid.height).step_by(2) {
+        for col in 0..grid.width {
+            let Rgb(r, g, b) = grid.get(top, col);
+            _ = write!(output, "\x1b[38;2;{};{};{}m", r, g, b);
+            if top + 1 < grid.height {
+                let Rgb(r, g, b) = grid.get(top + 1, col);
+                _ = write!(output, "\x1b[48;2;{};{};{}m", r, g, b);
+            } else {
+                output.push_str("\x1b[49m");
+            }
+            output.push('▀');
+        }
+        output.push_str("\x1b[0m\n");
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkers() -> Grid {
+        let mut grid = Grid::new(3, 2, Rgb::BLACK);
+        grid.set(0, 0, Rgb::RED);
+        grid.set(1, 1, Rgb::RED);
+        grid.set(0, 2, Rgb::RED);
+        grid.set(5, 5, Rgb::GREEN);
+        grid
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut bytes = Vec::new();
+        write_ppm(&checkers(), 2, &mut bytes).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&bytes[..header.len()], header);
+        let pixels = &bytes[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // the second pixel of the second row still belongs to cell (0, 0)
+        assert_eq!(&pixels[6 * 3 + 3..6 * 3 + 6], &[220, 50, 47]);
+        assert_eq!(&pixels[2 * 3..3 * 3], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn test_png() {
+        let mut bytes = Vec::new();
+        write_png(&checkers(), 1, &mut bytes).unwrap();
+        let mut reader = png::Decoder::new(&bytes[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(pixels, checkers().pixels(1));
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut bytes = Vec::new();
+        let small = Grid::new(1, 1, Rgb::BLUE);
+        write_gif(&[&checkers(), &small], 2, &mut bytes).unwrap();
+        assert_eq!(&bytes[..6], b"GIF89a");
+        assert_eq!(u16::from_le_bytes([bytes[6], bytes[7]]), 6);
+    }
+
+    #[test]
+    fn test_ansi() {
+        let rendered = ansi(&Grid::new(2, 3, Rgb::WHITE));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].matches('▀').count(), 2);
+        assert!(lines[0].contains("\x1b[48;2;230;230;230m"));
+        assert!(lines[1].contains("\x1b[49m"));
+    }
+}